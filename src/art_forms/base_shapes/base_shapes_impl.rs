@@ -1,30 +1,14 @@
 use geo::Point;
 use geo_types::CoordFloat;
-use svg::node::element::path::Data;
-use svg::node::element::path::Parameters;
-use svg::node::element::Circle;
-use svg::node::element::Path;
-use svg::node::Value;
-use svg::Node;
 
-type Canvas = svg::Document;
+use super::base_shapes_trait::{BaseShapes, Config};
+use crate::backend::Backend;
+use crate::path::Path;
 
-use super::base_shapes_trait::{BaseShapes, Config, SetConfig};
-
-/*====== BaseShapes Implementation for canvas ============ */
-impl<T: CoordFloat> BaseShapes<T> for Canvas
-where
-    Value: From<T>,
-    Parameters: From<T>,
-{
-    fn add_circle(self, radius: T, center: Point<T>, config: Config<T>) -> Canvas {
-        let mut circle = Circle::new();
-        circle.assign("cx", center.x());
-        circle.assign("cy", center.y());
-        circle.assign("r", radius);
-        circle = circle.set_config(config);
-
-        self.add(circle)
+/*====== BaseShapes Implementation for all backends ============ */
+impl<T: CoordFloat, B: Backend<T>> BaseShapes<T> for B {
+    fn add_circle(self, radius: T, center: Point<T>, config: Config<T>) -> Self {
+        self.draw_circle(radius, center, config)
     }
 
     /// Draw a polygon.
@@ -35,23 +19,10 @@ where
         config: Config<T>,
         should_close: bool,
     ) -> Self {
-        let start = polygon[0];
-        let polygon_data = polygon[1..]
-            .into_iter()
-            .fold(Data::new().move_to(start.x_y()), |polygon_data, point| {
-                polygon_data.line_to(point.x_y())
-            });
-
-        // Create a polygon path using the data, and style it
-        let polygon_path = Path::new().set_config(config).set(
-            "d",
-            match should_close {
-                false => polygon_data,
-                true => polygon_data.close(),
-            },
-        );
+        self.draw_path(Path::from_points(polygon, should_close), config)
+    }
 
-        // Add the ngon to the yantra
-        self.add(polygon_path)
+    fn add_path(self, path: Path<T>, config: Config<T>) -> Self {
+        self.draw_path(path, config)
     }
 }
@@ -1,24 +1,239 @@
 use crate::utils::dist;
 use crate::utils::intersection;
 use crate::utils::polar_point;
+use crate::error::{or_panic, Error, Result};
+use crate::paint::Paint;
+use crate::path;
+use super::corners::{soft_polygon, Corner, Edge};
+use super::curves::{
+    golden_rectangle_spiral, roulette_path, spiral_path, spline_path, Roulette, Spiral, Spline,
+};
 use derive_getters::Getters;
-use derive_new::new;
 use geo::GeoFloat;
 use geo::Point;
 use geo::Polygon;
 use num::integer;
+use svg::node::element::path::Parameters;
 use svg::node::element::Circle;
 use svg::node::element::Path;
 use svg::node::Value;
-use svg::node::element::path::Parameters;
 // use geo::GeoFloat;
 use geo_types::CoordFloat;
 
-#[derive(Clone, Getters, new)]
+/// Shape used at the ends of open strokes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineCap {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+/// Shape used where two segments of a stroke meet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineJoin {
+    Miter,
+    #[default]
+    Round,
+    Bevel,
+}
+
+/// Rule deciding which parts of a self-intersecting shape are inside.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FillRule {
+    #[default]
+    NonZero,
+    EvenOdd,
+}
+
+impl LineCap {
+    /// Value of the svg `stroke-linecap` attribute.
+    pub fn svg_value(&self) -> &'static str {
+        match self {
+            LineCap::Butt => "butt",
+            LineCap::Round => "round",
+            LineCap::Square => "square",
+        }
+    }
+}
+
+impl LineJoin {
+    /// Value of the svg `stroke-linejoin` attribute.
+    pub fn svg_value(&self) -> &'static str {
+        match self {
+            LineJoin::Miter => "miter",
+            LineJoin::Round => "round",
+            LineJoin::Bevel => "bevel",
+        }
+    }
+}
+
+impl FillRule {
+    /// Value of the svg `fill-rule` attribute.
+    pub fn svg_value(&self) -> &'static str {
+        match self {
+            FillRule::NonZero => "nonzero",
+            FillRule::EvenOdd => "evenodd",
+        }
+    }
+}
+
+/// Style of a shape.
+/// Only the stroke and fill are given to [`Config::new`], the other fields start
+/// with the svg defaults (except for round line joins) and are set with the `with_*` methods.
+#[derive(Clone, Debug, PartialEq, Getters)]
 pub struct Config<T: CoordFloat> {
     pub stroke_width: T,
+    /// Svg value of the stroke, like `"blue"`, `"none"` or the reference to a gradient.
     pub stroke_color: String,
+    /// Svg value of the fill, like `"blue"`, `"none"` or the reference to a gradient.
     pub fill_color: String,
+    /// Typed paint of the stroke, a colour, a gradient or a pattern.
+    /// It is used while `stroke_color` holds its svg value, so assigning another `stroke_color` replaces it.
+    pub stroke_paint: Option<Paint<T>>,
+    /// Typed paint of the fill, a colour, a gradient or a pattern.
+    /// It is used while `fill_color` holds its svg value, so assigning another `fill_color` replaces it.
+    pub fill_paint: Option<Paint<T>>,
+    pub fill_opacity: T,
+    pub stroke_opacity: T,
+    /// Lengths of alternating dashes and gaps, a solid stroke if empty.
+    pub dash_array: Vec<T>,
+    pub dash_offset: T,
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+    /// Limit on the ratio of miter length to stroke width, longer miters are beveled.
+    pub miter_limit: T,
+    pub fill_rule: FillRule,
+}
+
+impl<T: CoordFloat> Config<T> {
+    /// A config with the stroke and fill given as svg values, like `"blue"` or `"none"`.
+    /// They are written to svg documents as they are, see [`Config::try_new`] to check them.
+    pub fn new(stroke_width: T, stroke_color: String, fill_color: String) -> Self {
+        Config {
+            stroke_width,
+            stroke_color,
+            fill_color,
+            stroke_paint: None,
+            fill_paint: None,
+            fill_opacity: T::one(),
+            stroke_opacity: T::one(),
+            dash_array: vec![],
+            dash_offset: T::zero(),
+            line_cap: LineCap::default(),
+            line_join: LineJoin::default(),
+            miter_limit: T::from(4.0).unwrap(),
+            fill_rule: FillRule::default(),
+        }
+    }
+
+    /// A config with the stroke and fill painted by typed paints: [`Paint::None`],
+    /// a [`Color`](crate::color::Color), a gradient or a pattern.
+    pub fn from_paints<S, F>(stroke_width: T, stroke_paint: S, fill_paint: F) -> Self
+    where
+        S: Into<Paint<T>>,
+        F: Into<Paint<T>>,
+    {
+        Config::new(stroke_width, String::new(), String::new())
+            .with_stroke_paint(stroke_paint)
+            .with_fill_paint(fill_paint)
+    }
+
+    /// A config with the stroke and fill given as strings like `"blue"` or as typed paints,
+    /// failing if a string isn't `none` or a colour, e.g. for colours supplied by users.
+    pub fn try_new<S, F>(stroke_width: T, stroke_color: S, fill_color: F) -> Result<Self>
+    where
+        S: TryInto<Paint<T>>,
+        F: TryInto<Paint<T>>,
+        Error: From<S::Error> + From<F::Error>,
+    {
+        Ok(Config::from_paints(
+            stroke_width,
+            stroke_color.try_into()?,
+            fill_color.try_into()?,
+        ))
+    }
+
+    /// Paint the stroke with `paint`, its svg value becomes the `stroke_color`.
+    pub fn with_stroke_paint<P: Into<Paint<T>>>(mut self, paint: P) -> Self {
+        let paint = paint.into();
+        self.stroke_color = paint.svg_value();
+        self.stroke_paint = Some(paint);
+        self
+    }
+
+    /// Paint the fill with `paint`, its svg value becomes the `fill_color`.
+    pub fn with_fill_paint<P: Into<Paint<T>>>(mut self, paint: P) -> Self {
+        let paint = paint.into();
+        self.fill_color = paint.svg_value();
+        self.fill_paint = Some(paint);
+        self
+    }
+
+    /// Paint of the stroke: `stroke_paint` if `stroke_color` holds its svg value, otherwise
+    /// `stroke_color` parsed, [`Paint::None`] if it is neither `none` nor a colour.
+    pub fn stroke(&self) -> Paint<T> {
+        paint(&self.stroke_paint, &self.stroke_color)
+    }
+
+    /// Paint of the fill: `fill_paint` if `fill_color` holds its svg value, otherwise
+    /// `fill_color` parsed, [`Paint::None`] if it is neither `none` nor a colour.
+    pub fn fill(&self) -> Paint<T> {
+        paint(&self.fill_paint, &self.fill_color)
+    }
+
+    pub fn with_fill_opacity(mut self, fill_opacity: T) -> Self {
+        self.fill_opacity = fill_opacity;
+        self
+    }
+
+    pub fn with_stroke_opacity(mut self, stroke_opacity: T) -> Self {
+        self.stroke_opacity = stroke_opacity;
+        self
+    }
+
+    /// Dash the stroke with alternating dash and gap lengths, starting `dash_offset` into the pattern.
+    pub fn with_dashes(mut self, dash_array: Vec<T>, dash_offset: T) -> Self {
+        self.dash_array = dash_array;
+        self.dash_offset = dash_offset;
+        self
+    }
+
+    pub fn with_line_cap(mut self, line_cap: LineCap) -> Self {
+        self.line_cap = line_cap;
+        self
+    }
+
+    /// Join segments with `line_join`, `miter_limit` only matters for miter joins.
+    pub fn with_line_join(mut self, line_join: LineJoin, miter_limit: T) -> Self {
+        self.line_join = line_join;
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+}
+
+fn paint<T: CoordFloat>(paint: &Option<Paint<T>>, color: &str) -> Paint<T> {
+    match paint {
+        Some(paint) if paint.svg_value() == color => paint.clone(),
+        _ => color.parse().unwrap_or(Paint::None),
+    }
+}
+
+/// Where an element of a repeated art form is drawn, handed to per-element style closures.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Placement<T: CoordFloat> {
+    /// Position of the element in the order it is drawn.
+    pub index: usize,
+    /// Direction of the element in degrees, around the center of the art form.
+    pub angle: T,
+    /// Position of the element: the center of a circle, the first vertex of a star component,
+    /// or for a leaf the point on its circle at `angle`, halfway along its arc.
+    pub point: Point<T>,
 }
 
 pub trait SetConfig<T: CoordFloat> {
@@ -33,17 +248,42 @@ macro_rules! implement_set_config(
             Value: From<T>,
             Parameters: From<T>,
         {
+            /// Attributes holding their svg default are left out.
             #[inline]
             fn set_config(self, config: Config<T>) -> Self
-            // where
-            //     T: Into<Box<dyn crate::node::Node>>,
             {
-                self
-                .set("fill", config.fill_color.to_string())
-                .set("stroke", config.stroke_color.to_string())
-                .set("stroke-width", config.stroke_width)
-                .set("stroke-linejoin", "round")
-                .set("transform", "scale(1,-1)")
+                let mut element = self
+                    .set("fill", config.fill_color.clone())
+                    .set("stroke", config.stroke_color.clone())
+                    .set("stroke-width", config.stroke_width)
+                    .set("stroke-linejoin", config.line_join.svg_value());
+                if config.fill_opacity != T::one() {
+                    element = element.set("fill-opacity", config.fill_opacity);
+                }
+                if config.stroke_opacity != T::one() {
+                    element = element.set("stroke-opacity", config.stroke_opacity);
+                }
+                if !config.dash_array.is_empty() {
+                    let dash_array: Vec<String> = config
+                        .dash_array
+                        .iter()
+                        .map(|length| length.to_f64().unwrap().to_string())
+                        .collect();
+                    element = element.set("stroke-dasharray", dash_array.join(" "));
+                }
+                if config.dash_offset != T::zero() {
+                    element = element.set("stroke-dashoffset", config.dash_offset);
+                }
+                if config.line_cap != LineCap::Butt {
+                    element = element.set("stroke-linecap", config.line_cap.svg_value());
+                }
+                if config.line_join == LineJoin::Miter && config.miter_limit != T::from(4.0).unwrap() {
+                    element = element.set("stroke-miterlimit", config.miter_limit);
+                }
+                if config.fill_rule != FillRule::NonZero {
+                    element = element.set("fill-rule", config.fill_rule.svg_value());
+                }
+                element
             }
         }
     );
@@ -63,11 +303,33 @@ where
     fn add_line_string(self, polygon: Vec<Point<T>>, config: Config<T>, should_close: bool)
         -> Self;
 
+    /// Add any path, see [`path::Path`].
+    fn add_path(self, path: path::Path<T>, config: Config<T>) -> Self;
+
     fn add_circles(self, radius: T, centers: Vec<Point<T>>, config: Config<T>) -> Self {
-        let yantra = centers.into_iter().fold(self, |yantra, point| {
-            yantra.add_circle(radius, point, config.clone())
-        });
-        yantra
+        self.add_circles_with(radius, centers, |_| config.clone())
+    }
+
+    /// Add circles styled one by one by `style`.
+    /// The angle of a circle is the direction of its center seen from the centroid of all centers.
+    fn add_circles_with<F>(self, radius: T, centers: Vec<Point<T>>, style: F) -> Self
+    where
+        F: Fn(Placement<T>) -> Config<T>,
+    {
+        let count = T::from(centers.len().max(1)).unwrap();
+        let centroid = centers
+            .iter()
+            .fold(Point::new(T::zero(), T::zero()), |sum, point| sum + *point)
+            / count;
+        centers
+            .into_iter()
+            .enumerate()
+            .fold(self, |yantra, (index, point)| {
+                let direction = point - centroid;
+                let angle = direction.y().atan2(direction.x()).to_degrees();
+                let config = style(Placement { index, angle, point });
+                yantra.add_circle(radius, point, config)
+            })
     }
 
     /// Polygon closed
@@ -90,16 +352,32 @@ where
         n: usize,
         config: Config<T>,
     ) -> Self {
-        assert!(n >= 3, "n gon must have at least three sides");
+        or_panic(self.try_add_regular_n_gon(radius, center, alpha, n, config))
+    }
+
+    /// Add a regular n-gon, failing if `n < 3`.
+    fn try_add_regular_n_gon(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        n: usize,
+        config: Config<T>,
+    ) -> Result<Self> {
+        if n < 3 {
+            return Err(Error::TooFewVertices(n));
+        }
         let side_span = T::from(360.0).unwrap() / T::from(n).unwrap();
         let polygon = (0..n)
             .map(|i| alpha + T::from(i).unwrap() * side_span)
             .map(|cur_alpha| polar_point(radius, center, cur_alpha))
             .collect();
-        self.add_polygon(polygon, config)
+        Ok(self.add_polygon(polygon, config))
     }
 
-    /// Add a [Star polygon](https://en.wikipedia.org/wiki/Star_polygon)
+    /// Add a [Star polygon](https://en.wikipedia.org/wiki/Star_polygon).
+    /// If `p` and `q` aren't coprime this is the compound of `gcd(p, q)` rotated stars,
+    /// e.g. the hexagram {6/2} of two triangles.
     fn add_star_polygon(
         self,
         radius: T,
@@ -109,27 +387,74 @@ where
         q: usize,
         config: Config<T>,
     ) -> Self {
-        assert!(q > 0, "q > 0 in order to avoid infinite loops.");
-        assert!(p >= 3, "a polygon must have at least three vertices");
-        assert!(integer::gcd(p, q) == 1, "invalid input to star polygon");
-        let side_span = T::from(360.0).unwrap() / T::from(p).unwrap();
-        let polygon: Vec<Point<T>> = (0..p)
-            .map(|i| alpha + T::from(i).unwrap() * side_span)
-            .map(|cur_alpha| polar_point(radius, center, cur_alpha))
-            .collect();
+        or_panic(self.try_add_star_polygon(radius, center, alpha, p, q, config))
+    }
 
-        // let start = polar_point(radius, center, cur_alpha);
-        let mut idx = q;
-        let mut star = vec![polygon[0]];
-        while polygon[idx] != polygon[0] {
-            star.push(polygon[idx]);
-            idx += q;
-            idx %= p;
-        }
+    /// Add a [Star polygon](https://en.wikipedia.org/wiki/Star_polygon),
+    /// failing unless `q > 0` and the components have at least three vertices.
+    fn try_add_star_polygon(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        p: usize,
+        q: usize,
+        config: Config<T>,
+    ) -> Result<Self> {
+        self.try_add_star_polygon_with(radius, center, alpha, p, q, |_| config.clone())
+    }
 
-        self.add_polygon(star, config)
+    /// Add a star polygon whose components are styled one by one by `style`,
+    /// e.g. to colour the up and down triangles of a hexagram differently.
+    /// Component `k` starts at vertex `k`, its angle is the one of that vertex.
+    #[allow(clippy::too_many_arguments)]
+    fn add_star_polygon_with<F>(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        p: usize,
+        q: usize,
+        style: F,
+    ) -> Self
+    where
+        F: Fn(Placement<T>) -> Config<T>,
+    {
+        or_panic(self.try_add_star_polygon_with(radius, center, alpha, p, q, style))
     }
 
+    /// Add a star polygon whose components are styled one by one by `style`,
+    /// failing unless `q > 0` and the components have at least three vertices.
+    #[allow(clippy::too_many_arguments)]
+    fn try_add_star_polygon_with<F>(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        p: usize,
+        q: usize,
+        style: F,
+    ) -> Result<Self>
+    where
+        F: Fn(Placement<T>) -> Config<T>,
+    {
+        let components = star_components(radius, center, alpha, p, q)?;
+        let side_span = T::from(360.0).unwrap() / T::from(p).unwrap();
+        Ok(components
+            .into_iter()
+            .enumerate()
+            .fold(self, |shapes, (index, star)| {
+                let angle = alpha + T::from(index).unwrap() * side_span;
+                let config = style(Placement {
+                    index,
+                    angle,
+                    point: star[0],
+                });
+                shapes.add_polygon(star, config)
+            }))
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn add_isotoxal_star(
         self,
         radius: T,
@@ -143,34 +468,493 @@ where
     where
         T: GeoFloat,
     {
-        let remainder = q % p;
-        assert!(
-            remainder > 0 && remainder != 1 && remainder != p - 1,
-            "Condition on remainder needed for a concave shape."
-        );
-        assert!(p >= 3, "a polygon must have at least three vertices");
-        assert!(integer::gcd(p, q) == 1, "invalid input to star polygon");
-        let side_span = T::from(360.0).unwrap() / T::from(p).unwrap();
-        let start = polar_point(radius, center, alpha);
-        let mid = polar_point(radius, center, alpha + side_span / T::from(2.0).unwrap());
-        let first_edge_to = polar_point(radius, center, alpha + T::from(q).unwrap() * side_span);
-        let intersection = intersection(start, first_edge_to, center, mid);
-        let smaller_radius = dist(center, intersection);
-
-        let star = (0..2 * p)
-            .map(|i| match i % 2 {
-                0 => polar_point(radius, center, alpha + T::from(i / 2).unwrap() * side_span),
-                1 => polar_point(
+        or_panic(self.try_add_isotoxal_star(radius, center, alpha, p, q, config, deformation))
+    }
+
+    /// Add an isotoxal star, failing unless `p >= 3`, `p`, `q` are coprime
+    /// and `q mod p` is neither `0`, `1` nor `p - 1`.
+    #[allow(clippy::too_many_arguments)]
+    fn try_add_isotoxal_star(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        p: usize,
+        q: usize,
+        config: Config<T>,
+        deformation: Option<usize>,
+    ) -> Result<Self>
+    where
+        T: GeoFloat,
+    {
+        let star = isotoxal_star(radius, center, alpha, p, q, deformation)?;
+        Ok(self.add_polygon(star, config))
+    }
+
+    /// Add an ellipse with semi-axes `radii`, the first one rotated by `rotation` degrees from the x-axis.
+    fn add_ellipse(self, radii: (T, T), center: Point<T>, rotation: T, config: Config<T>) -> Self {
+        let half_turn = T::from(180.0).unwrap();
+        let start = polar_point(radii.0, center, rotation);
+        let opposite = polar_point(radii.0, center, rotation + half_turn);
+        let ellipse = path::Path::new()
+            .move_to(start)
+            .arc_to(radii, rotation, false, true, opposite)
+            .arc_to(radii, rotation, false, true, start)
+            .close();
+        self.add_path(ellipse, config)
+    }
+
+    /// Add an open circular arc from the angle `alpha` to `beta` in degrees,
+    /// counter-clockwise if `beta > alpha`.
+    fn add_arc(self, radius: T, center: Point<T>, alpha: T, beta: T, config: Config<T>) -> Self {
+        let arc = path::Path::new().move_to(polar_point(radius, center, alpha));
+        self.add_path(arc_to(arc, radius, center, alpha, beta), config)
+    }
+
+    /// Add a sector of the ring between `inner_radius` and `outer_radius`,
+    /// from the angle `alpha` to `beta` in degrees.
+    fn add_annular_sector(
+        self,
+        inner_radius: T,
+        outer_radius: T,
+        center: Point<T>,
+        alpha: T,
+        beta: T,
+        config: Config<T>,
+    ) -> Self {
+        or_panic(self.try_add_annular_sector(
+            inner_radius,
+            outer_radius,
+            center,
+            alpha,
+            beta,
+            config,
+        ))
+    }
+
+    /// Add a sector of a ring, failing unless `0 <= inner_radius <= outer_radius`
+    /// and both radii are finite.
+    fn try_add_annular_sector(
+        self,
+        inner_radius: T,
+        outer_radius: T,
+        center: Point<T>,
+        alpha: T,
+        beta: T,
+        config: Config<T>,
+    ) -> Result<Self> {
+        let ordered = T::zero() <= inner_radius && inner_radius <= outer_radius;
+        if !(ordered && outer_radius.is_finite()) {
+            return Err(Error::InvalidRadius);
+        }
+        let sector = path::Path::new().move_to(polar_point(outer_radius, center, alpha));
+        let sector = arc_to(sector, outer_radius, center, alpha, beta)
+            .line_to(polar_point(inner_radius, center, beta));
+        let sector = arc_to(sector, inner_radius, center, beta, alpha).close();
+        Ok(self.add_path(sector, config))
+    }
+
+    /// Add the ring between `inner_radius` and `outer_radius` as one path with a real hole,
+    /// whatever the fill rule: the inner circle runs the other way around.
+    fn add_annulus(self, inner_radius: T, outer_radius: T, center: Point<T>, config: Config<T>) -> Self {
+        let full_turn = T::from(360.0).unwrap();
+        let zero = T::zero();
+        let annulus = path::Path::new().move_to(polar_point(outer_radius, center, zero));
+        let annulus = arc_to(annulus, outer_radius, center, zero, full_turn)
+            .close()
+            .move_to(polar_point(inner_radius, center, zero));
+        let annulus = arc_to(annulus, inner_radius, center, zero, -full_turn).close();
+        self.add_path(annulus, config)
+    }
+
+    /// Add a closed polygon with softened corners and edges.
+    /// Corner `i` is drawn as `corners[i % corners.len()]`, so a single corner applies to all of them.
+    fn add_soft_polygon(
+        self,
+        polygon: Vec<Point<T>>,
+        corners: &[Corner<T>],
+        edge: Edge<T>,
+        config: Config<T>,
+    ) -> Self {
+        self.add_path(soft_polygon(&polygon, corners, edge), config)
+    }
+
+    /// Add a regular n-gon with softened corners and edges, see [`BaseShapes::add_soft_polygon`].
+    #[allow(clippy::too_many_arguments)]
+    fn add_soft_regular_n_gon(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        n: usize,
+        corners: &[Corner<T>],
+        edge: Edge<T>,
+        config: Config<T>,
+    ) -> Self {
+        or_panic(self.try_add_soft_regular_n_gon(radius, center, alpha, n, corners, edge, config))
+    }
+
+    /// Add a regular n-gon with softened corners and edges, failing if `n < 3`.
+    #[allow(clippy::too_many_arguments)]
+    fn try_add_soft_regular_n_gon(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        n: usize,
+        corners: &[Corner<T>],
+        edge: Edge<T>,
+        config: Config<T>,
+    ) -> Result<Self> {
+        let polygon = star_components(radius, center, alpha, n, 1)?.remove(0);
+        Ok(self.add_soft_polygon(polygon, corners, edge, config))
+    }
+
+    /// Add a star polygon, or a compound of stars, with softened corners and edges,
+    /// e.g. stars and triangles with rounded tips. Corners are counted from the first tip of every component.
+    #[allow(clippy::too_many_arguments)]
+    fn add_soft_star_polygon(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        p: usize,
+        q: usize,
+        corners: &[Corner<T>],
+        edge: Edge<T>,
+        config: Config<T>,
+    ) -> Self {
+        or_panic(self.try_add_soft_star_polygon(radius, center, alpha, p, q, corners, edge, config))
+    }
+
+    /// Add a star polygon with softened corners and edges,
+    /// failing unless `q > 0` and the components have at least three vertices.
+    #[allow(clippy::too_many_arguments)]
+    fn try_add_soft_star_polygon(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        p: usize,
+        q: usize,
+        corners: &[Corner<T>],
+        edge: Edge<T>,
+        config: Config<T>,
+    ) -> Result<Self> {
+        Ok(star_components(radius, center, alpha, p, q)?
+            .into_iter()
+            .fold(self, |shapes, star| {
+                shapes.add_soft_polygon(star, corners, edge, config.clone())
+            }))
+    }
+
+    /// Add a spiral starting at `start_radius` from `center` at the angle `alpha`, turning `turns` times
+    /// counter-clockwise, or clockwise if `turns` is negative.
+    /// The spiral is drawn with cubic Bezier curves at most `tolerance` away from it.
+    #[allow(clippy::too_many_arguments)]
+    fn add_spiral(
+        self,
+        spiral: Spiral<T>,
+        start_radius: T,
+        center: Point<T>,
+        alpha: T,
+        turns: T,
+        tolerance: T,
+        config: Config<T>,
+    ) -> Self {
+        or_panic(self.try_add_spiral(
+            spiral,
+            start_radius,
+            center,
+            alpha,
+            turns,
+            tolerance,
+            config,
+        ))
+    }
+
+    /// Add a spiral, failing unless `turns` is finite, `tolerance` is positive and finite and the growth
+    /// of `spiral` is finite, and positive for a logarithmic spiral.
+    #[allow(clippy::too_many_arguments)]
+    fn try_add_spiral(
+        self,
+        spiral: Spiral<T>,
+        start_radius: T,
+        center: Point<T>,
+        alpha: T,
+        turns: T,
+        tolerance: T,
+        config: Config<T>,
+    ) -> Result<Self> {
+        let spiral = spiral_path(spiral, start_radius, center, alpha, turns, tolerance)?;
+        Ok(self.add_path(spiral, config))
+    }
+
+    /// Add the golden spiral of quarter circles through `quarter_turns` nested squares of a golden rectangle,
+    /// the largest square of side `size`. The spiral starts at `start` heading at the angle `alpha`
+    /// and turns counter-clockwise inwards. The squares are drawn too with `squares`, if given.
+    #[allow(clippy::too_many_arguments)]
+    fn add_golden_rectangle_spiral(
+        self,
+        size: T,
+        start: Point<T>,
+        alpha: T,
+        quarter_turns: usize,
+        config: Config<T>,
+        squares: Option<Config<T>>,
+    ) -> Self {
+        or_panic(self.try_add_golden_rectangle_spiral(
+            size,
+            start,
+            alpha,
+            quarter_turns,
+            config,
+            squares,
+        ))
+    }
+
+    /// Add the golden spiral of quarter circles through nested squares of a golden rectangle,
+    /// failing if `size` is negative or not finite.
+    #[allow(clippy::too_many_arguments)]
+    fn try_add_golden_rectangle_spiral(
+        self,
+        size: T,
+        start: Point<T>,
+        alpha: T,
+        quarter_turns: usize,
+        config: Config<T>,
+        squares: Option<Config<T>>,
+    ) -> Result<Self> {
+        let (spiral, rectangles) = golden_rectangle_spiral(size, start, alpha, quarter_turns)?;
+        let shapes = match squares {
+            Some(squares) => rectangles
+                .into_iter()
+                .fold(self, |shapes, square| shapes.add_polygon(square, squares.clone())),
+            None => self,
+        };
+        Ok(shapes.add_path(spiral, config))
+    }
+
+    /// Add a rose or a spirograph of size `radius` around `center`, rotated by `alpha`, closed after its period.
+    /// The size is the radius of a rose, or of the fixed circle of a spirograph.
+    /// The curve is drawn with cubic Bezier curves at most `tolerance` away from it.
+    fn add_roulette(
+        self,
+        roulette: Roulette<T>,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        tolerance: T,
+        config: Config<T>,
+    ) -> Self {
+        or_panic(self.try_add_roulette(roulette, radius, center, alpha, tolerance, config))
+    }
+
+    /// Add a rose or a spirograph, failing if one of the terms of its ratio is zero or `tolerance`
+    /// is not positive and finite.
+    fn try_add_roulette(
+        self,
+        roulette: Roulette<T>,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        tolerance: T,
+        config: Config<T>,
+    ) -> Result<Self> {
+        let roulette = roulette_path(roulette, radius, center, alpha, tolerance)?;
+        Ok(self.add_path(roulette, config))
+    }
+
+    /// Add a quadratic Bezier curve from `start` to `end`.
+    fn add_quadratic_bezier(
+        self,
+        start: Point<T>,
+        control: Point<T>,
+        end: Point<T>,
+        config: Config<T>,
+    ) -> Self {
+        self.add_path(path::Path::new().move_to(start).quadratic_to(control, end), config)
+    }
+
+    /// Add a cubic Bezier curve from `start` to `end`.
+    fn add_cubic_bezier(
+        self,
+        start: Point<T>,
+        control_1: Point<T>,
+        control_2: Point<T>,
+        end: Point<T>,
+        config: Config<T>,
+    ) -> Self {
+        self.add_path(path::Path::new().move_to(start).cubic_to(control_1, control_2, end), config)
+    }
+
+    /// Add a smooth open curve through `points`, a Catmull-Rom spline with knots spaced as `spline`.
+    fn add_spline(self, points: Vec<Point<T>>, spline: Spline, config: Config<T>) -> Self {
+        self.add_path(spline_path(&points, spline, false), config)
+    }
+
+    /// Add a smooth closed loop through `points`, a closed Catmull-Rom spline with knots spaced as `spline`.
+    fn add_smooth_loop(self, points: Vec<Point<T>>, spline: Spline, config: Config<T>) -> Self {
+        or_panic(self.try_add_smooth_loop(points, spline, config))
+    }
+
+    /// Add a smooth closed loop through `points`, failing if there are less than three points.
+    fn try_add_smooth_loop(
+        self,
+        points: Vec<Point<T>>,
+        spline: Spline,
+        config: Config<T>,
+    ) -> Result<Self> {
+        if points.len() < 3 {
+            return Err(Error::TooFewVertices(points.len()));
+        }
+        Ok(self.add_path(spline_path(&points, spline, true), config))
+    }
+}
+
+/// Vertices of the isotoxal star `{p/q}`, alternately on the outer and the inner circle.
+fn isotoxal_star<T: CoordFloat + GeoFloat>(
+    radius: T,
+    center: Point<T>,
+    alpha: T,
+    p: usize,
+    q: usize,
+    deformation: Option<usize>,
+) -> Result<Vec<Point<T>>> {
+    if p < 3 {
+        return Err(Error::TooFewVertices(p));
+    }
+    let remainder = q % p;
+    if remainder == 0 || remainder == 1 || remainder == p - 1 {
+        return Err(Error::NotConcave { p, q });
+    }
+    if integer::gcd(p, q) != 1 {
+        return Err(Error::NotCoprime { p, q });
+    }
+    let side_span = T::from(360.0).unwrap() / T::from(p).unwrap();
+    let start = polar_point(radius, center, alpha);
+    let mid = polar_point(radius, center, alpha + side_span / T::from(2.0).unwrap());
+    let first_edge_to = polar_point(radius, center, alpha + T::from(q).unwrap() * side_span);
+    let intersection = intersection(start, first_edge_to, center, mid)?;
+    let smaller_radius = dist(center, intersection);
+
+    Ok((0..2 * p)
+        .map(|i| {
+            if i % 2 == 0 {
+                polar_point(radius, center, alpha + T::from(i / 2).unwrap() * side_span)
+            } else {
+                polar_point(
                     smaller_radius,
                     center,
                     alpha
                         + side_span / T::from(2.0).unwrap()
                         + T::from((i + deformation.unwrap_or(0)) / 2).unwrap() * side_span,
-                ),
-                _ => panic!("I don't understand this remainder!"),
-            })
-            .collect();
+                )
+            }
+        })
+        .collect())
+}
+
+/// Outline of the star polygon `{p/q}` drawn by [`BaseShapes::add_star_polygon`],
+/// one closed sub-path per component.
+pub fn star_polygon_outline<T: CoordFloat>(
+    radius: T,
+    center: Point<T>,
+    alpha: T,
+    p: usize,
+    q: usize,
+) -> path::Path<T> {
+    or_panic(try_star_polygon_outline(radius, center, alpha, p, q))
+}
+
+/// Outline of the star polygon `{p/q}`, failing unless `q > 0` and the components have at least three vertices.
+pub fn try_star_polygon_outline<T: CoordFloat>(
+    radius: T,
+    center: Point<T>,
+    alpha: T,
+    p: usize,
+    q: usize,
+) -> Result<path::Path<T>> {
+    Ok(star_components(radius, center, alpha, p, q)?
+        .into_iter()
+        .fold(path::Path::new(), |outline, star| {
+            star.into_iter()
+                .enumerate()
+                .fold(outline, |outline, (i, point)| match i {
+                    0 => outline.move_to(point),
+                    _ => outline.line_to(point),
+                })
+                .close()
+        }))
+}
 
-        self.add_polygon(star, config)
+/// Outline of the isotoxal star drawn by [`BaseShapes::add_isotoxal_star`].
+pub fn isotoxal_star_outline<T: CoordFloat + GeoFloat>(
+    radius: T,
+    center: Point<T>,
+    alpha: T,
+    p: usize,
+    q: usize,
+    deformation: Option<usize>,
+) -> path::Path<T> {
+    or_panic(try_isotoxal_star_outline(radius, center, alpha, p, q, deformation))
+}
+
+/// Outline of the isotoxal star, failing as [`BaseShapes::try_add_isotoxal_star`] does.
+pub fn try_isotoxal_star_outline<T: CoordFloat + GeoFloat>(
+    radius: T,
+    center: Point<T>,
+    alpha: T,
+    p: usize,
+    q: usize,
+    deformation: Option<usize>,
+) -> Result<path::Path<T>> {
+    let star = isotoxal_star(radius, center, alpha, p, q, deformation)?;
+    Ok(path::Path::from_points(star, true))
+}
+
+/// Vertices of the `gcd(p, q)` stars making up the star polygon `{p/q}`,
+/// the component `k` starts at the vertex `k`.
+fn star_components<T: CoordFloat>(
+    radius: T,
+    center: Point<T>,
+    alpha: T,
+    p: usize,
+    q: usize,
+) -> Result<Vec<Vec<Point<T>>>> {
+    if q == 0 {
+        return Err(Error::ZeroStep);
     }
+    if p < 3 {
+        return Err(Error::TooFewVertices(p));
+    }
+    let components = integer::gcd(p, q);
+    let vertices = p / components;
+    if vertices < 3 {
+        return Err(Error::TooFewVertices(vertices));
+    }
+    let side_span = T::from(360.0).unwrap() / T::from(p).unwrap();
+    let vertex = |i: usize| polar_point(radius, center, alpha + T::from(i % p).unwrap() * side_span);
+    Ok((0..components)
+        .map(|index| (0..vertices).map(|i| vertex(index + i * q)).collect())
+        .collect())
+}
+
+/// Continue `path`, which is at the angle `alpha` on the circle, along the circle to the angle `beta`.
+/// The arc is split in pieces of at most half a turn, so the large arc flag is never needed.
+fn arc_to<T: CoordFloat>(
+    path: path::Path<T>,
+    radius: T,
+    center: Point<T>,
+    alpha: T,
+    beta: T,
+) -> path::Path<T> {
+    let half_turn = T::from(180.0).unwrap();
+    let pieces = ((beta - alpha).abs() / half_turn).ceil().max(T::one());
+    let step = (beta - alpha) / pieces;
+    let sweep = beta > alpha;
+    (1..=pieces.to_usize().unwrap_or(1)).fold(path, |path, i| {
+        let angle = alpha + step * T::from(i).unwrap();
+        path.arc_to((radius, radius), T::zero(), false, sweep, polar_point(radius, center, angle))
+    })
 }
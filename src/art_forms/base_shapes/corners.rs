@@ -0,0 +1,163 @@
+//! Softened polygons: corners rounded by fillets or cut by chamfers, and edges bulged outwards or inwards.
+use geo::Point;
+use geo_types::CoordFloat;
+
+use crate::path::Path;
+
+/// How a corner of a polygon is drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Corner<T: CoordFloat> {
+    /// The vertex itself.
+    Sharp,
+    /// An arc of the given radius tangent to both edges.
+    Fillet(T),
+    /// A straight cut starting at the given distance from the vertex on both edges.
+    Chamfer(T),
+}
+
+/// How the edges of a polygon are drawn between its corners.
+/// Bulges are the height of the middle of an edge above its chord, relative to the length of the chord:
+/// positive bulges go outwards, negative ones inwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Edge<T: CoordFloat> {
+    #[default]
+    Straight,
+    /// A circular arc with the given bulge.
+    Arc(T),
+    /// A quadratic Bezier curve with the given bulge.
+    Quadratic(T),
+}
+
+fn cross<T: CoordFloat>(p: Point<T>, q: Point<T>) -> T {
+    p.x() * q.y() - p.y() * q.x()
+}
+
+fn norm<T: CoordFloat>(p: Point<T>) -> T {
+    p.x().hypot(p.y())
+}
+
+/// Where a corner starts and ends on its edges, and the radius and direction of its fillet.
+#[derive(Clone, Copy)]
+struct Trim<T: CoordFloat> {
+    start: Point<T>,
+    end: Point<T>,
+    corner: Corner<T>,
+    radius: T,
+    left_turn: bool,
+}
+
+/// Continue `path` from `start` to `end` along an edge.
+/// The outside of the polygon is on the right for a counter-clockwise polygon.
+fn edge_to<T: CoordFloat>(
+    path: Path<T>,
+    start: Point<T>,
+    end: Point<T>,
+    edge: Edge<T>,
+    counter_clockwise: bool,
+) -> Path<T> {
+    let two = T::from(2.0).unwrap();
+    let chord = end - start;
+    let length = norm(chord);
+    let bulge = match edge {
+        Edge::Straight => T::zero(),
+        Edge::Arc(bulge) | Edge::Quadratic(bulge) => bulge,
+    };
+    if length <= T::epsilon() || bulge == T::zero() {
+        return path.line_to(end);
+    }
+    let height = bulge * length;
+    match edge {
+        Edge::Arc(_) => {
+            let radius = (height * height + length * length / (two * two)) / (two * height.abs());
+            let large_arc = height.abs() > length / two;
+            let sweep = (bulge > T::zero()) == counter_clockwise;
+            path.arc_to((radius, radius), T::zero(), large_arc, sweep, end)
+        }
+        _ => {
+            let right = Point::new(chord.y(), -chord.x()) / length;
+            let outwards = if counter_clockwise { right } else { -right };
+            // The middle of a quadratic curve is halfway between its chord and its control point
+            let control = (start + end) / two + outwards * (two * height);
+            path.quadratic_to(control, end)
+        }
+    }
+}
+
+/// A closed path around `points` with the corners drawn as `corners`, repeated if there are
+/// less corners than points, and edges drawn as `edge`.
+/// Fillets and chamfers are shrunk to fit in half of the adjacent edges.
+pub(crate) fn soft_polygon<T: CoordFloat>(
+    points: &[Point<T>],
+    corners: &[Corner<T>],
+    edge: Edge<T>,
+) -> Path<T> {
+    let n = points.len();
+    if n < 3 {
+        return Path::from_points(points.to_vec(), true);
+    }
+    let two = T::from(2.0).unwrap();
+    let counter_clockwise = (0..n)
+        .map(|i| cross(points[i], points[(i + 1) % n]))
+        .fold(T::zero(), |area, part| area + part)
+        > T::zero();
+
+    let trimmed: Vec<Trim<T>> = (0..n)
+        .map(|i| {
+            let (previous, vertex, next) =
+                (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+            let (to_previous, to_next) = (previous - vertex, next - vertex);
+            let (previous_length, next_length) = (norm(to_previous), norm(to_next));
+            let corner = corners
+                .get(i % corners.len().max(1))
+                .copied()
+                .unwrap_or(Corner::Sharp);
+            let angle = cross(to_previous, to_next)
+                .abs()
+                .atan2(to_previous.dot(to_next));
+            let half_tan = (angle / two).tan();
+            let wanted = match corner {
+                Corner::Sharp => T::zero(),
+                Corner::Fillet(radius) => radius / half_tan,
+                Corner::Chamfer(distance) => distance,
+            };
+            let distance = wanted.min(previous_length / two).min(next_length / two);
+            if distance.is_nan() || distance <= T::epsilon() {
+                return Trim {
+                    start: vertex,
+                    end: vertex,
+                    corner: Corner::Sharp,
+                    radius: T::zero(),
+                    left_turn: false,
+                };
+            }
+            Trim {
+                start: vertex + to_previous * (distance / previous_length),
+                end: vertex + to_next * (distance / next_length),
+                corner,
+                radius: distance * half_tan,
+                left_turn: cross(vertex - previous, next - vertex) > T::zero(),
+            }
+        })
+        .collect();
+
+    let path = Path::new().move_to(trimmed[0].end);
+    (1..=n)
+        .fold(path, |path, i| {
+            let Trim {
+                start,
+                end,
+                corner,
+                radius,
+                left_turn,
+            } = trimmed[i % n];
+            let path = edge_to(path, trimmed[i - 1].end, start, edge, counter_clockwise);
+            match corner {
+                Corner::Sharp => path,
+                Corner::Fillet(_) => {
+                    path.arc_to((radius, radius), T::zero(), false, left_turn, end)
+                }
+                Corner::Chamfer(_) => path.line_to(end),
+            }
+        })
+        .close()
+}
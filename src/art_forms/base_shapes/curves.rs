@@ -0,0 +1,445 @@
+//! Smooth curves: parametric curves fitted with cubic Bezier curves, spirals, roses, spirographs and splines.
+use geo::{point, Point};
+use geo_types::CoordFloat;
+use num::integer;
+
+use crate::error::{or_panic, Error, Result};
+use crate::path::Path;
+use crate::utils::golden_ratio;
+
+/// Deepest halving of a piece of curve that is still too far from its Bezier curve.
+const MAX_DEPTH: usize = 16;
+
+fn norm<T: CoordFloat>(p: Point<T>) -> T {
+    p.x().hypot(p.y())
+}
+
+fn cubic_at<T: CoordFloat>(points: [Point<T>; 4], t: T) -> Point<T> {
+    let s = T::one() - t;
+    let three = T::from(3.0).unwrap();
+    points[0] * (s * s * s)
+        + points[1] * (three * s * s * t)
+        + points[2] * (three * s * t * t)
+        + points[3] * (t * t * t)
+}
+
+/// Continue `path` along `curve` from `t0` to `t1` with cubic Bezier curves matching the curve
+/// and its derivative at their ends, halving the piece while it is more than `tolerance` off.
+fn fit_piece<T, F>(path: Path<T>, curve: &F, t0: T, t1: T, tolerance: T, depth: usize) -> Path<T>
+where
+    T: CoordFloat,
+    F: Fn(T) -> (Point<T>, Point<T>),
+{
+    let ((start, start_derivative), (end, end_derivative)) = (curve(t0), curve(t1));
+    let third = (t1 - t0) / T::from(3.0).unwrap();
+    let bezier = [
+        start,
+        start + start_derivative * third,
+        end - end_derivative * third,
+        end,
+    ];
+    let error = [0.25, 0.5, 0.75]
+        .iter()
+        .map(|&s| {
+            let s = T::from(s).unwrap();
+            norm(cubic_at(bezier, s) - curve(t0 + (t1 - t0) * s).0)
+        })
+        .fold(T::zero(), T::max);
+    if depth == 0 || error <= tolerance {
+        return path.cubic_to(bezier[1], bezier[2], end);
+    }
+    let middle = (t0 + t1) / T::from(2.0).unwrap();
+    let path = fit_piece(path, curve, t0, middle, tolerance, depth - 1);
+    fit_piece(path, curve, middle, t1, tolerance, depth - 1)
+}
+
+/// Check that a tolerance can be met by halving pieces of a curve a finite number of times.
+pub(crate) fn check_tolerance<T: CoordFloat>(tolerance: T) -> Result<()> {
+    match tolerance > T::zero() && tolerance.is_finite() {
+        true => Ok(()),
+        false => Err(Error::InvalidTolerance),
+    }
+}
+
+/// An open path along `curve`, a function from a parameter to a point and the derivative there,
+/// from `start` to `end`. The curve is cut into `pieces` pieces first, each is then fitted with
+/// cubic Bezier curves at most `tolerance` away from the curve.
+pub(crate) fn fit_curve<T, F>(curve: F, start: T, end: T, pieces: usize, tolerance: T) -> Path<T>
+where
+    T: CoordFloat,
+    F: Fn(T) -> (Point<T>, Point<T>),
+{
+    let pieces = pieces.max(1);
+    let step = (end - start) / T::from(pieces).unwrap();
+    (0..pieces).fold(Path::new().move_to(curve(start).0), |path, i| {
+        let t0 = start + step * T::from(i).unwrap();
+        let t1 = if i + 1 == pieces { end } else { t0 + step };
+        fit_piece(path, &curve, t0, t1, tolerance, MAX_DEPTH)
+    })
+}
+
+/// The point at the `radius` and `angle` (in radians) from `center`, and its derivative,
+/// given the derivatives of the radius and the angle.
+fn polar<T: CoordFloat>(
+    center: Point<T>,
+    (radius, radius_derivative): (T, T),
+    (angle, angle_derivative): (T, T),
+) -> (Point<T>, Point<T>) {
+    let (sin, cos) = angle.sin_cos();
+    let outwards = point!(x: cos, y: sin);
+    let along = point!(x: -sin, y: cos);
+    (
+        center + outwards * radius,
+        outwards * radius_derivative + along * (radius * angle_derivative),
+    )
+}
+
+/// A spiral, the radius growing (or shrinking) from a start radius `r0` with the number of turns `t`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Spiral<T: CoordFloat> {
+    /// `r = r0 + growth·t`, successive turns are `growth` apart.
+    Archimedean(T),
+    /// `r = r0·growth^t`, the radius is multiplied by `growth` every turn.
+    Logarithmic(T),
+    /// `r² = r0² + growth²·t`, the area between successive turns is `π·growth²`.
+    Fermat(T),
+    /// The logarithmic spiral whose radius is multiplied by the golden ratio every quarter turn.
+    Golden,
+}
+
+impl<T: CoordFloat> Spiral<T> {
+    /// Radius after `t` turns from `start_radius`.
+    pub fn radius(&self, start_radius: T, t: T) -> T {
+        match *self {
+            Spiral::Archimedean(growth) => start_radius + growth * t,
+            Spiral::Logarithmic(growth) => start_radius * growth.powf(t),
+            Spiral::Fermat(growth) => (start_radius * start_radius + growth * growth * t).sqrt(),
+            Spiral::Golden => start_radius * golden_ratio::<T>().powf(T::from(4.0).unwrap() * t),
+        }
+    }
+    /// Check that the growth gives a spiral: it must be finite, and positive for a logarithmic spiral.
+    fn check_growth(&self) -> Result<()> {
+        let valid = match *self {
+            Spiral::Archimedean(growth) | Spiral::Fermat(growth) => growth.is_finite(),
+            Spiral::Logarithmic(growth) => growth > T::zero() && growth.is_finite(),
+            Spiral::Golden => true,
+        };
+        match valid {
+            true => Ok(()),
+            false => Err(Error::InvalidGrowth),
+        }
+    }
+}
+
+/// An open path along `spiral` starting at `start_radius` from `center` at the angle `alpha` in degrees,
+/// turning `turns` times counter-clockwise, or clockwise if `turns` is negative.
+/// Fails if the growth of `spiral`, `turns` or `tolerance` is invalid.
+pub(crate) fn spiral_path<T: CoordFloat>(
+    spiral: Spiral<T>,
+    start_radius: T,
+    center: Point<T>,
+    alpha: T,
+    turns: T,
+    tolerance: T,
+) -> Result<Path<T>> {
+    spiral.check_growth()?;
+    if !turns.is_finite() {
+        return Err(Error::InvalidTurns);
+    }
+    check_tolerance(tolerance)?;
+    let full_turn = T::from(std::f64::consts::TAU).unwrap();
+    let direction = turns.signum();
+    let alpha = alpha.to_radians();
+    let quarters = (T::from(4.0).unwrap() * turns.abs())
+        .ceil()
+        .to_usize()
+        .unwrap_or(1);
+    let path = match spiral {
+        // The radius of a Fermat spiral starting at its center grows infinitely fast,
+        // its parameter is the square root of the number of turns so that it stays smooth.
+        Spiral::Fermat(growth) => fit_curve(
+            |s: T| {
+                let radius = spiral.radius(start_radius, s * s);
+                let radius_derivative = match radius > T::zero() {
+                    true => growth * growth * s / radius,
+                    false => growth,
+                };
+                let angle = (
+                    alpha + direction * full_turn * s * s,
+                    direction * full_turn * (s + s),
+                );
+                polar(center, (radius, radius_derivative), angle)
+            },
+            T::zero(),
+            turns.abs().sqrt(),
+            2 * quarters,
+            tolerance,
+        ),
+        _ => fit_curve(
+            |t: T| {
+                let radius = spiral.radius(start_radius, t);
+                let radius_derivative = match spiral {
+                    Spiral::Archimedean(growth) => growth,
+                    Spiral::Logarithmic(growth) => radius * growth.ln(),
+                    _ => radius * T::from(4.0).unwrap() * golden_ratio::<T>().ln(),
+                };
+                let angle = (alpha + direction * full_turn * t, direction * full_turn);
+                polar(center, (radius, radius_derivative), angle)
+            },
+            T::zero(),
+            turns.abs(),
+            quarters,
+            tolerance,
+        ),
+    };
+    Ok(path)
+}
+
+/// Corners of the nested squares of a golden rectangle, from the largest inwards.
+type Squares<T> = Vec<Vec<Point<T>>>;
+
+/// The quarter circles through `quarter_turns` nested squares of a golden rectangle, from the largest square
+/// of side `size` inwards, and the squares. The spiral starts at `start` heading at the angle `alpha`
+/// in degrees and turns counter-clockwise. Fails if `size` is negative or not finite.
+pub(crate) fn golden_rectangle_spiral<T: CoordFloat>(
+    size: T,
+    start: Point<T>,
+    alpha: T,
+    quarter_turns: usize,
+) -> Result<(Path<T>, Squares<T>)> {
+    if !(size >= T::zero() && size.is_finite()) {
+        return Err(Error::InvalidSize);
+    }
+    let (sin, cos) = alpha.to_radians().sin_cos();
+    let heading = point!(x: cos, y: sin);
+    let spiral = Path::new().move_to(start);
+    let squares = vec![];
+    let (spiral, squares, ..) = (0..quarter_turns).fold(
+        (spiral, squares, start, heading, size),
+        |(spiral, mut squares, point, heading, side), _| {
+            let left = point!(x: -heading.y(), y: heading.x());
+            let arc_center = point + left * side;
+            let end = arc_center + heading * side;
+            squares.push(vec![point, point + heading * side, end, arc_center]);
+            let spiral = spiral.arc_to((side, side), T::zero(), false, true, end);
+            (spiral, squares, end, left, side / golden_ratio())
+        },
+    );
+    Ok((spiral, squares))
+}
+
+/// A closed curve traced by a point of a circle rolling on a fixed circle, with the ratios given by teeth counts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Roulette<T: CoordFloat> {
+    /// The rose `r = cos(k·θ)` with `k = n / d`.
+    Rose { n: usize, d: usize },
+    /// A circle with `rolling` teeth rolling inside a circle with `fixed` teeth,
+    /// the pen `pen` times the radius of the rolling circle away from its center.
+    Hypotrochoid {
+        fixed: usize,
+        rolling: usize,
+        pen: T,
+    },
+    /// A circle with `rolling` teeth rolling outside a circle with `fixed` teeth,
+    /// the pen `pen` times the radius of the rolling circle away from its center.
+    Epitrochoid {
+        fixed: usize,
+        rolling: usize,
+        pen: T,
+    },
+}
+
+impl<T: CoordFloat> Roulette<T> {
+    /// The ratio, failing if one of its terms is zero.
+    fn ratio(&self) -> Result<(usize, usize)> {
+        let (n, d) = match *self {
+            Roulette::Rose { n, d } => (n, d),
+            Roulette::Hypotrochoid { fixed, rolling, .. }
+            | Roulette::Epitrochoid { fixed, rolling, .. } => (fixed, rolling),
+        };
+        match n > 0 && d > 0 {
+            true => Ok((n, d)),
+            false => Err(Error::ZeroRatioTerm { n, d }),
+        }
+    }
+
+    /// The range of angles, in radians, after which the curve closes.
+    pub fn period(&self) -> T {
+        or_panic(self.try_period())
+    }
+
+    /// The range of angles after which the curve closes, failing if one of the terms of its ratio is zero.
+    pub fn try_period(&self) -> Result<T> {
+        let (n, d) = self.ratio()?;
+        let turns = d / integer::gcd(n, d);
+        let half_turns = match self {
+            // A rose with odd n and d is traced twice over a full period
+            Roulette::Rose { .. } if (n / integer::gcd(n, d)) % 2 == 1 && turns % 2 == 1 => turns,
+            _ => 2 * turns,
+        };
+        Ok(T::from(half_turns).unwrap() * T::from(std::f64::consts::PI).unwrap())
+    }
+
+    /// The point at the angle `theta` on the curve of size `radius` around the origin, and its derivative.
+    fn at(&self, radius: T, theta: T) -> (Point<T>, Point<T>) {
+        let ratio = |n: usize, d: usize| T::from(n).unwrap() / T::from(d).unwrap();
+        let (sin, cos) = theta.sin_cos();
+        match *self {
+            Roulette::Rose { n, d } => {
+                let k = ratio(n, d);
+                let (k_sin, k_cos) = (k * theta).sin_cos();
+                polar(
+                    point!(x: T::zero(), y: T::zero()),
+                    (radius * k_cos, -radius * k * k_sin),
+                    (theta, T::one()),
+                )
+            }
+            Roulette::Hypotrochoid {
+                fixed,
+                rolling,
+                pen,
+            } => {
+                let rolling_radius = radius * ratio(rolling, fixed);
+                let (arm, pen) = (radius - rolling_radius, pen * rolling_radius);
+                let m = arm / rolling_radius;
+                let (m_sin, m_cos) = (m * theta).sin_cos();
+                (
+                    point!(x: arm * cos + pen * m_cos, y: arm * sin - pen * m_sin),
+                    point!(x: -arm * sin - pen * m * m_sin, y: arm * cos - pen * m * m_cos),
+                )
+            }
+            Roulette::Epitrochoid {
+                fixed,
+                rolling,
+                pen,
+            } => {
+                let rolling_radius = radius * ratio(rolling, fixed);
+                let (arm, pen) = (radius + rolling_radius, pen * rolling_radius);
+                let m = arm / rolling_radius;
+                let (m_sin, m_cos) = (m * theta).sin_cos();
+                (
+                    point!(x: arm * cos - pen * m_cos, y: arm * sin - pen * m_sin),
+                    point!(x: -arm * sin + pen * m * m_sin, y: arm * cos - pen * m * m_cos),
+                )
+            }
+        }
+    }
+}
+
+/// A closed path along `roulette` of size `radius` around `center`, rotated by `alpha` degrees,
+/// with cubic Bezier curves at most `tolerance` away from it.
+/// The size is the radius of a rose, or of the fixed circle of a spirograph.
+/// Fails if a term of the ratio of `roulette` is zero or `tolerance` is invalid.
+pub(crate) fn roulette_path<T: CoordFloat>(
+    roulette: Roulette<T>,
+    radius: T,
+    center: Point<T>,
+    alpha: T,
+    tolerance: T,
+) -> Result<Path<T>> {
+    check_tolerance(tolerance)?;
+    let period = roulette.try_period()?;
+    let (n, d) = roulette.ratio()?;
+    let (sin, cos) = alpha.to_radians().sin_cos();
+    let rotate = |p: Point<T>| point!(x: p.x() * cos - p.y() * sin, y: p.x() * sin + p.y() * cos);
+    // at least four pieces for every turn of the pen around either circle
+    let turns = (period / T::from(std::f64::consts::PI).unwrap())
+        .to_usize()
+        .unwrap();
+    let pieces = 4 * turns * (n + d) / d.min(n);
+    let path = fit_curve(
+        |theta| {
+            let (point, derivative) = roulette.at(radius, theta);
+            (center + rotate(point), rotate(derivative))
+        },
+        T::zero(),
+        period,
+        pieces,
+        tolerance,
+    );
+    Ok(path.close())
+}
+
+/// How the knots of a Catmull-Rom spline are spaced, from the distances between its points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Spline {
+    /// Evenly spaced knots, the classic Catmull-Rom spline.
+    Uniform,
+    /// Knots spaced by the square root of the distances, without loops or cusps within a segment.
+    #[default]
+    Centripetal,
+    /// Knots spaced by the distances, hugging the points more tightly.
+    Chordal,
+}
+
+impl Spline {
+    fn exponent<T: CoordFloat>(&self) -> T {
+        T::from(match self {
+            Spline::Uniform => 0.0,
+            Spline::Centripetal => 0.5,
+            Spline::Chordal => 1.0,
+        })
+        .unwrap()
+    }
+}
+
+/// The cubic Bezier control points of the Catmull-Rom segment from `p1` to `p2`,
+/// with knot intervals `d1`, `d2`, `d3` between the four points.
+fn catmull_rom_controls<T: CoordFloat>(
+    [p0, p1, p2, p3]: [Point<T>; 4],
+    [d1, d2, d3]: [T; 3],
+) -> (Point<T>, Point<T>) {
+    let (two, three) = (T::from(2.0).unwrap(), T::from(3.0).unwrap());
+    // a repeated point has no tangent to follow, the curve leaves it straight
+    let control_1 = match d1 > T::epsilon() {
+        true => {
+            (p2 * (d1 * d1) - p0 * (d2 * d2) + p1 * (two * d1 * d1 + three * d1 * d2 + d2 * d2))
+                / (three * d1 * (d1 + d2))
+        }
+        false => p1,
+    };
+    let control_2 = match d3 > T::epsilon() {
+        true => {
+            (p1 * (d3 * d3) - p3 * (d2 * d2) + p2 * (two * d3 * d3 + three * d3 * d2 + d2 * d2))
+                / (three * d3 * (d3 + d2))
+        }
+        false => p2,
+    };
+    (control_1, control_2)
+}
+
+/// A smooth path of cubic Bezier curves through `points`, closed into a loop if `closed`.
+/// The ends of an open spline continue the first and last segments.
+pub(crate) fn spline_path<T: CoordFloat>(
+    points: &[Point<T>],
+    spline: Spline,
+    closed: bool,
+) -> Path<T> {
+    let n = points.len();
+    if n < 2 || (closed && n < 3) {
+        return Path::from_points(points.to_vec(), closed);
+    }
+    let two = T::from(2.0).unwrap();
+    let point = |i: isize| match (closed, i) {
+        (true, _) => points[i.rem_euclid(n as isize) as usize],
+        (false, -1) => points[0] * two - points[1],
+        (false, i) if i as usize == n => points[n - 1] * two - points[n - 2],
+        (false, i) => points[i as usize],
+    };
+    let knot = |p: Point<T>, q: Point<T>| norm(q - p).powf(spline.exponent());
+    let segments = if closed { n } else { n - 1 } as isize;
+    let path = (0..segments).fold(Path::new().move_to(points[0]), |path, i| {
+        let quad = [point(i - 1), point(i), point(i + 1), point(i + 2)];
+        let knots = [
+            knot(quad[0], quad[1]),
+            knot(quad[1], quad[2]),
+            knot(quad[2], quad[3]),
+        ];
+        let (control_1, control_2) = catmull_rom_controls(quad, knots);
+        path.cubic_to(control_1, control_2, quad[2])
+    });
+    match closed {
+        true => path.close(),
+        false => path,
+    }
+}
@@ -1,6 +1,13 @@
 pub mod base_shapes_trait;
 pub mod base_shapes_impl;
-pub use base_shapes_trait::{BaseShapes, Config, SetConfig};
+pub mod corners;
+pub mod curves;
+pub use corners::{Corner, Edge};
+pub use curves::{Roulette, Spiral, Spline};
+pub use base_shapes_trait::{
+    isotoxal_star_outline, star_polygon_outline, try_isotoxal_star_outline, try_star_polygon_outline,
+    BaseShapes, Config, FillRule, LineCap, LineJoin, Placement, SetConfig,
+};
 
 #[cfg(test)]
 pub mod test_base_shapes;
\ No newline at end of file
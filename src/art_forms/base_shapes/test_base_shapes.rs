@@ -1,10 +1,10 @@
-use super::{Config, BaseShapes};
+use super::{Config, BaseShapes, FillRule, LineCap, LineJoin};
 
+use crate::canvas::SvgCanvas;
 use geo::point;
 use geo::polygon;
-use svg::Document;
 
-type Canvas = Document;
+type Canvas = SvgCanvas;
 type T = f64;
 
 #[test]
@@ -104,3 +104,774 @@ fn test_add_isotoxal_star() {
 
 
 
+#[test]
+fn test_config_attributes() {
+    let view_size = 100.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let plain = Config::<T>::try_new(3.0, "blue", "yellow").unwrap();
+    let styled = plain
+        .clone()
+        .with_fill_opacity(0.5)
+        .with_stroke_opacity(0.8)
+        .with_dashes(vec![6.0, 3.0], 1.5)
+        .with_line_cap(LineCap::Round)
+        .with_line_join(LineJoin::Miter, 10.0)
+        .with_fill_rule(FillRule::EvenOdd);
+    let canvas = canvas
+        .add_star_polygon(40.0, point!(x: -50.0, y: 0.0), 90.0, 5, 2, plain)
+        .add_star_polygon(40.0, point!(x: 50.0, y: 0.0), 90.0, 5, 2, styled);
+    let content = canvas.to_string();
+    // defaults are left out
+    assert_eq!(content.matches("fill-opacity").count(), 1);
+    assert_eq!(content.matches(r#"stroke-linejoin="round""#).count(), 1);
+    for attribute in [
+        r#"fill-opacity="0.5""#,
+        r#"stroke-opacity="0.8""#,
+        r#"stroke-dasharray="6 3""#,
+        r#"stroke-dashoffset="1.5""#,
+        r#"stroke-linecap="round""#,
+        r#"stroke-linejoin="miter""#,
+        r#"stroke-miterlimit="10""#,
+        r#"fill-rule="evenodd""#,
+    ] {
+        assert!(content.contains(attribute), "{} is missing", attribute);
+    }
+    svg::save("./unit_tests/base_shapes/config_attributes.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_add_circles_with_style() {
+    use crate::scene::Scene;
+    let centers: Vec<_> = (0..4)
+        .map(|i| {
+            let (sin, cos) = (90.0 * i as T).to_radians().sin_cos();
+            point!(x: 10.0 + 50.0 * cos, y: 20.0 + 50.0 * sin)
+        })
+        .collect();
+    let scene = Scene::new().add_circles_with(10.0, centers.clone(), |placement| {
+        assert_eq!(placement.point, centers[placement.index]);
+        let angle = [0.0, 90.0, 180.0, -90.0][placement.index];
+        assert!((placement.angle - angle).abs() < 1e-9);
+        let fill = if placement.angle > 0.0 { "red" } else { "blue" };
+        Config::try_new(1.0, "none", fill).unwrap()
+    });
+    let fills: Vec<String> = scene
+        .shapes()
+        .iter()
+        .map(|shape| shape.config.fill().svg_value())
+        .collect();
+    assert_eq!(fills, ["#0000ff", "#ff0000", "#ff0000", "#0000ff"]);
+}
+
+#[test]
+fn test_try_variants() {
+    use crate::error::Error;
+    let canvas = Canvas::new();
+    let center = point!(x: 0.0, y: 0.0);
+    let config = Config::<T>::try_new(1.0, "black", "none").unwrap();
+    let error = |result: Result<Canvas, Error>| result.err();
+    assert_eq!(
+        error(
+            canvas
+                .clone()
+                .try_add_regular_n_gon(10.0, center, 0.0, 2, config.clone())
+        ),
+        Some(Error::TooFewVertices(2))
+    );
+    assert_eq!(
+        error(
+            canvas
+                .clone()
+                .try_add_star_polygon(10.0, center, 0.0, 7, 0, config.clone())
+        ),
+        Some(Error::ZeroStep)
+    );
+    assert_eq!(
+        error(
+            canvas
+                .clone()
+                .try_add_star_polygon(10.0, center, 0.0, 6, 3, config.clone())
+        ),
+        Some(Error::TooFewVertices(2))
+    );
+    assert_eq!(
+        error(canvas.clone().try_add_isotoxal_star(
+            10.0,
+            center,
+            0.0,
+            8,
+            10,
+            config.clone(),
+            None
+        )),
+        Some(Error::NotCoprime { p: 8, q: 10 })
+    );
+    assert_eq!(
+        error(
+            canvas
+                .clone()
+                .try_add_isotoxal_star(10.0, center, 0.0, 7, 8, config.clone(), None)
+        ),
+        Some(Error::NotConcave { p: 7, q: 8 })
+    );
+    let star = canvas
+        .clone()
+        .try_add_star_polygon(10.0, center, 0.0, 7, 3, config.clone())
+        .unwrap();
+    assert_eq!(
+        star.to_string(),
+        canvas
+            .add_star_polygon(10.0, center, 0.0, 7, 3, config)
+            .to_string()
+    );
+
+    assert!(Config::<T>::try_new(1.0, "kumkum", "none").is_ok());
+    assert_eq!(
+        Config::<T>::try_new(1.0, "black", "#E8FB6"),
+        Err(Error::Color(crate::color::ParseColorError::InvalidHex(
+            "#E8FB6".to_string()
+        )))
+    );
+}
+
+#[test]
+#[should_panic(expected = "a polygon must have at least three vertices, got 2")]
+fn test_invalid_n_gon_panics() {
+    let config = Config::<T>::try_new(1.0, "black", "none").unwrap();
+    Canvas::new().add_regular_n_gon(10.0, point!(x: 0.0, y: 0.0), 0.0, 2, config);
+}
+
+#[test]
+fn test_add_compound_star_polygon() {
+    use crate::path::PathCommand;
+    use crate::scene::{Geometry, Scene};
+    let view_size = 100.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let center = point!(x: 0.0, y: 0.0);
+    // the shatkona, an up and a down triangle
+    let shatkona = |placement: super::Placement<T>| match placement.index {
+        0 => Config::try_new(1.0, "black", "kumkum").unwrap(),
+        _ => Config::try_new(1.0, "black", "neel").unwrap(),
+    };
+    let scene = Scene::new().add_star_polygon_with(80.0, center, 90.0, 6, 2, shatkona);
+    assert_eq!(scene.shapes().len(), 2);
+    let tips: Vec<T> = scene
+        .shapes()
+        .iter()
+        .map(|shape| match &shape.geometry {
+            Geometry::Path(path) => match path.commands()[0] {
+                PathCommand::MoveTo(point) => point.y(),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        })
+        .collect();
+    assert!((tips[0] - 80.0).abs() < 1e-9);
+    assert!((tips[1] - 40.0).abs() < 1e-9);
+    assert_eq!(scene.shapes()[1].config.fill().svg_value(), "#1f3b73");
+
+    // the octagram {8/3} is a single star, {8/2} is the compound of two squares
+    let config = Config::<T>::try_new(1.0, "black", "none").unwrap();
+    let octagrams = Scene::new()
+        .add_star_polygon(80.0, center, 0.0, 8, 3, config.clone())
+        .add_star_polygon(60.0, center, 0.0, 8, 2, config.clone())
+        .add_star_polygon(40.0, center, 0.0, 12, 3, config);
+    assert_eq!(octagrams.shapes().len(), 1 + 2 + 3);
+
+    let canvas = scene.render(octagrams.render(canvas));
+    svg::save("./unit_tests/base_shapes/add_compound_star_polygon.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_add_curved_shapes() {
+    use crate::backend::RasterCanvas;
+    use crate::scene::Scene;
+    use geo::Rect;
+    let view_size = 100.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let center = point!(x: 0.0, y: 0.0);
+    let scene = Scene::new()
+        .add_circle(95.0, center, Config::try_new(0.0, "none", "haldi").unwrap())
+        .add_annulus(
+            60.0,
+            90.0,
+            center,
+            Config::try_new(1.0, "black", "kumkum").unwrap(),
+        )
+        .add_annular_sector(
+            30.0,
+            55.0,
+            center,
+            0.0,
+            135.0,
+            Config::try_new(1.0, "black", "neel").unwrap(),
+        )
+        .add_ellipse(
+            (25.0, 10.0),
+            center,
+            45.0,
+            Config::try_new(1.0, "black", "white").unwrap(),
+        )
+        .add_arc(
+            40.0,
+            center,
+            180.0,
+            450.0,
+            Config::try_new(2.0, "black", "none").unwrap(),
+        );
+
+    // the annulus has a real hole, even with the default nonzero fill rule
+    let top = |point| scene.shapes_at(point).first().map(|shape| shape.config.fill().svg_value());
+    assert_eq!(top(point!(x: 0.0, y: 75.0)).as_deref(), Some("#f33625"));
+    assert_eq!(top(point!(x: -58.0, y: 0.0)).as_deref(), Some("#e3a018"));
+    // the sector spans the angles from 0 to 135 degrees
+    assert_eq!(top(point!(x: 0.0, y: 45.0)).as_deref(), Some("#1f3b73"));
+    assert_eq!(top(point!(x: 0.0, y: -45.0)).as_deref(), Some("#e3a018"));
+    // the ellipse is rotated by 45 degrees
+    assert_eq!(top(point!(x: 14.0, y: 14.0)).as_deref(), Some("#ffffff"));
+    assert_eq!(top(point!(x: 14.0, y: -14.0)).as_deref(), Some("#e3a018"));
+
+    // angles are in degrees as in `polar_point`, the open arc ends at 90 degrees
+    // (arcs are measured on their Bezier approximation)
+    let bounds: Rect<T> = scene.shapes()[4].bounds().unwrap();
+    assert!((bounds.max().y - 41.0).abs() < 1e-2);
+    assert!((bounds.min().y + 41.0).abs() < 1e-2);
+
+    let raster = scene.render(RasterCanvas::new(
+        200,
+        200,
+        Rect::new(point!(x: -100.0, y: -100.0), point!(x: 100.0, y: 100.0)),
+    ));
+    assert_eq!(raster.pixel(100, 25), [0xf3, 0x36, 0x25, 255]);
+    assert_eq!(raster.pixel(42, 100), [0xe3, 0xa0, 0x18, 255]);
+
+    let canvas = scene.render(canvas);
+    svg::save("./unit_tests/base_shapes/add_curved_shapes.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_add_soft_polygons() {
+    use crate::art_forms::base_shapes::{Corner, Edge};
+    use crate::scene::Scene;
+    let view_size = 100.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let config = Config::try_new(1.0, "black", "kesar").unwrap();
+    let scene = Scene::new()
+        .add_soft_regular_n_gon(
+            40.0,
+            point!(x: -50.0, y: 50.0),
+            90.0,
+            3,
+            &[Corner::Fillet(10.0)],
+            Edge::Straight,
+            config.clone(),
+        )
+        .add_soft_regular_n_gon(
+            40.0,
+            point!(x: 50.0, y: 50.0),
+            45.0,
+            4,
+            &[Corner::Chamfer(10.0), Corner::Sharp],
+            Edge::Straight,
+            config.clone(),
+        )
+        .add_soft_regular_n_gon(
+            40.0,
+            point!(x: -50.0, y: -50.0),
+            0.0,
+            6,
+            &[Corner::Sharp],
+            Edge::Arc(-0.2),
+            config.clone(),
+        )
+        .add_soft_star_polygon(
+            40.0,
+            point!(x: 50.0, y: -50.0),
+            90.0,
+            6,
+            2,
+            &[Corner::Fillet(3.0)],
+            Edge::Quadratic(0.1),
+            config,
+        );
+    assert_eq!(scene.shapes().len(), 5);
+
+    let inside = |x, y| !scene.shapes_at(point!(x: x, y: y)).is_empty();
+    // the tip of the triangle is rounded off
+    assert!(inside(-50.0, 50.0));
+    assert!(!inside(-50.0, 88.0));
+    // only every other corner of the square is cut
+    assert!(inside(70.0, 70.0));
+    assert!(!inside(77.0, 77.0));
+    assert!(inside(23.0, 77.0));
+    // the edges of the hexagon bulge inwards, by a fifth of their length
+    let (cos, sin) = (30f64.to_radians().cos(), 30f64.to_radians().sin());
+    assert!(inside(-50.0 + 20.0 * cos, -50.0 + 20.0 * sin));
+    assert!(!inside(-50.0 + 31.0 * cos, -50.0 + 31.0 * sin));
+
+    let canvas = scene.render(canvas);
+    svg::save("./unit_tests/base_shapes/add_soft_polygons.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_add_spirals() {
+    use super::Spiral;
+    use crate::path::PathCommand;
+    use crate::scene::{Geometry, Scene};
+    let view_size = 100.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let config = Config::<T>::try_new(1.0, "black", "none").unwrap();
+    let center = point!(x: 0.0, y: 0.0);
+    let commands = |scene: &Scene<T>, i: usize| match &scene.shapes()[i].geometry {
+        Geometry::Path(path) => path.commands().to_vec(),
+        _ => unreachable!(),
+    };
+
+    // the Archimedean spiral is at most the tolerance away from r = 10 + 10·t
+    let spiral = Scene::new().add_spiral(
+        Spiral::Archimedean(10.0),
+        10.0,
+        center,
+        0.0,
+        3.0,
+        0.01,
+        config.clone(),
+    );
+    let spiral = commands(&spiral, 0);
+    assert!(
+        matches!(spiral.last(), Some(PathCommand::CubicTo(_, _, end)) if (end.x() - 40.0).abs() < 1e-9 && end.y().abs() < 1e-9)
+    );
+    let mut start = center;
+    for command in &spiral {
+        match *command {
+            PathCommand::MoveTo(point) => start = point,
+            PathCommand::CubicTo(control_1, control_2, end) => {
+                let middle = (start + end) * 0.125 + (control_1 + control_2) * 0.375;
+                let turns = middle.y().atan2(middle.x()).to_degrees().rem_euclid(360.0) / 360.0;
+                let distance = (0..3)
+                    .map(|turn| {
+                        (middle.x().hypot(middle.y()) - 10.0 * (1.0 + turns + turn as T)).abs()
+                    })
+                    .fold(T::INFINITY, T::min);
+                assert!(distance < 0.02, "{} off the spiral", distance);
+                start = end;
+            }
+            _ => unreachable!(),
+        }
+    }
+    // a coarser tolerance takes less curves
+    let coarse = Scene::new().add_spiral(
+        Spiral::Archimedean(10.0),
+        10.0,
+        center,
+        0.0,
+        3.0,
+        1.0,
+        config.clone(),
+    );
+    assert!(commands(&coarse, 0).len() < spiral.len());
+
+    let scene = Scene::new()
+        .add_spiral(
+            Spiral::Logarithmic(1.5),
+            10.0,
+            center,
+            90.0,
+            -4.0,
+            0.05,
+            config.clone(),
+        )
+        .add_spiral(
+            Spiral::Fermat(20.0),
+            0.0,
+            center,
+            0.0,
+            4.0,
+            0.05,
+            Config::try_new(1.0, "kumkum", "none").unwrap(),
+        )
+        .add_spiral(
+            Spiral::Golden,
+            1.0,
+            center,
+            0.0,
+            1.5,
+            0.05,
+            Config::try_new(1.0, "neel", "none").unwrap(),
+        )
+        .add_golden_rectangle_spiral(
+            60.0,
+            point!(x: -50.0, y: -90.0),
+            0.0,
+            8,
+            Config::try_new(1.0, "haldi", "none").unwrap(),
+            Some(Config::try_new(0.5, "black", "none").unwrap()),
+        );
+    // the logarithmic spiral turns clockwise, 1.5 times larger every turn
+    let end = |commands: Vec<PathCommand<T>>| match commands.last() {
+        Some(PathCommand::CubicTo(_, _, end)) | Some(PathCommand::ArcTo(_, _, _, _, _, end)) => {
+            *end
+        }
+        _ => unreachable!(),
+    };
+    let logarithmic = end(commands(&scene, 0));
+    assert!(logarithmic.x().abs() < 1e-9 && (logarithmic.y() - 10.0 * 1.5f64.powi(4)).abs() < 1e-9);
+    let fermat = end(commands(&scene, 1));
+    assert!((fermat.x() - 40.0).abs() < 1e-9);
+    let golden = end(commands(&scene, 2));
+    assert!((golden.x() + 1.0 * (1.0 + 5f64.sqrt()).powi(6) / 64.0).abs() < 1e-9);
+    // eight squares, then the spiral through them
+    assert_eq!(scene.shapes().len(), 3 + 8 + 1);
+    assert!(matches!(commands(&scene, 11)[1], PathCommand::ArcTo(radius, ..) if radius == 60.0));
+
+    let canvas = scene.render(canvas);
+    svg::save("./unit_tests/base_shapes/add_spirals.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_spirals_reject_invalid_parameters() {
+    use super::Spiral;
+    use crate::error::Error;
+    let config = Config::<T>::try_new(1.0, "black", "none").unwrap();
+    let center = point!(x: 0.0, y: 0.0);
+    let spiral = |spiral: Spiral<T>, tolerance: T| {
+        Canvas::new()
+            .try_add_spiral(spiral, 10.0, center, 0.0, 3.0, tolerance, config.clone())
+            .err()
+    };
+    for tolerance in [0.0, -1.0, T::NAN, T::INFINITY] {
+        assert_eq!(
+            spiral(Spiral::Archimedean(10.0), tolerance),
+            Some(Error::InvalidTolerance)
+        );
+    }
+    for growth in [0.0, -1.5, T::NAN] {
+        assert_eq!(
+            spiral(Spiral::Logarithmic(growth), 0.05),
+            Some(Error::InvalidGrowth)
+        );
+    }
+    assert_eq!(
+        spiral(Spiral::Fermat(T::INFINITY), 0.05),
+        Some(Error::InvalidGrowth)
+    );
+    assert_eq!(spiral(Spiral::Logarithmic(1.5), 0.05), None);
+}
+
+#[test]
+fn test_spirals_reject_invalid_turns() {
+    use super::Spiral;
+    use crate::error::Error;
+    let config = Config::<T>::try_new(1.0, "black", "none").unwrap();
+    let center = point!(x: 0.0, y: 0.0);
+    let spiral = |turns: T| {
+        Canvas::new()
+            .try_add_spiral(
+                Spiral::Archimedean(10.0),
+                10.0,
+                center,
+                0.0,
+                turns,
+                0.05,
+                config.clone(),
+            )
+            .err()
+    };
+    for turns in [T::NAN, T::INFINITY, T::NEG_INFINITY] {
+        assert_eq!(spiral(turns), Some(Error::InvalidTurns));
+    }
+    // negative turns turn clockwise
+    assert_eq!(spiral(-2.0), None);
+}
+
+#[test]
+fn test_golden_rectangle_spiral_rejects_invalid_sizes() {
+    use crate::error::Error;
+    let config = Config::<T>::try_new(1.0, "black", "none").unwrap();
+    let spiral = |size: T| {
+        Canvas::new()
+            .try_add_golden_rectangle_spiral(
+                size,
+                point!(x: 0.0, y: 0.0),
+                0.0,
+                4,
+                config.clone(),
+                None,
+            )
+            .err()
+    };
+    for size in [-1.0, T::NAN, T::INFINITY] {
+        assert_eq!(spiral(size), Some(Error::InvalidSize));
+    }
+    assert_eq!(spiral(60.0), None);
+}
+
+#[test]
+fn test_annular_sector_rejects_invalid_radii() {
+    use crate::error::Error;
+    let config = Config::<T>::try_new(1.0, "black", "none").unwrap();
+    let sector = |inner_radius: T, outer_radius: T| {
+        Canvas::new()
+            .try_add_annular_sector(
+                inner_radius,
+                outer_radius,
+                point!(x: 0.0, y: 0.0),
+                0.0,
+                90.0,
+                config.clone(),
+            )
+            .err()
+    };
+    for (inner_radius, outer_radius) in [
+        (-1.0, 10.0),
+        (T::NAN, 10.0),
+        (5.0, T::NAN),
+        (5.0, T::INFINITY),
+        (20.0, 10.0),
+    ] {
+        assert_eq!(
+            sector(inner_radius, outer_radius),
+            Some(Error::InvalidRadius)
+        );
+    }
+    // a sector without hole is a slice of a disc
+    assert_eq!(sector(0.0, 10.0), None);
+}
+
+#[test]
+fn test_add_roulettes() {
+    use super::Roulette;
+    use crate::error::Error;
+    use crate::path::PathCommand;
+    use crate::scene::{Geometry, Scene};
+    use geo::Point;
+    use std::f64::consts::PI;
+    let view_size = 100.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let center = point!(x: 0.0, y: 0.0);
+
+    // the period is found from the ratio
+    assert_eq!(Roulette::<T>::Rose { n: 3, d: 1 }.period(), PI);
+    assert_eq!(Roulette::<T>::Rose { n: 4, d: 1 }.period(), 2.0 * PI);
+    assert_eq!(Roulette::<T>::Rose { n: 2, d: 4 }.period(), 4.0 * PI);
+    assert_eq!(Roulette::<T>::Rose { n: 3, d: 5 }.period(), 5.0 * PI);
+    let spirograph = Roulette::Hypotrochoid {
+        fixed: 96,
+        rolling: 36,
+        pen: 0.8,
+    };
+    assert_eq!(spirograph.period(), 6.0 * PI);
+    assert_eq!(
+        Roulette::<T>::Epitrochoid {
+            fixed: 0,
+            rolling: 3,
+            pen: 1.0
+        }
+        .try_period(),
+        Err(Error::ZeroRatioTerm { n: 0, d: 3 })
+    );
+    for tolerance in [0.0, -0.05, T::NAN] {
+        assert_eq!(
+            Canvas::new()
+                .try_add_roulette(
+                    Roulette::Rose { n: 5, d: 1 },
+                    40.0,
+                    center,
+                    0.0,
+                    tolerance,
+                    Config::try_new(1.0, "black", "none").unwrap(),
+                )
+                .err(),
+            Some(Error::InvalidTolerance)
+        );
+    }
+
+    let scene = Scene::new()
+        .add_roulette(
+            spirograph,
+            90.0,
+            center,
+            0.0,
+            0.05,
+            Config::try_new(0.5, "neel", "none").unwrap(),
+        )
+        .add_roulette(
+            Roulette::Rose { n: 5, d: 1 },
+            40.0,
+            center,
+            90.0,
+            0.05,
+            Config::try_new(1.0, "black", "kumkum").unwrap(),
+        )
+        .add_roulette(
+            Roulette::Hypotrochoid {
+                fixed: 3,
+                rolling: 1,
+                pen: 1.0,
+            },
+            40.0,
+            center,
+            90.0,
+            0.05,
+            Config::try_new(1.0, "black", "none").unwrap(),
+        )
+        .add_roulette(
+            Roulette::Epitrochoid {
+                fixed: 1,
+                rolling: 1,
+                pen: 1.0,
+            },
+            10.0,
+            center,
+            0.0,
+            0.05,
+            Config::try_new(1.0, "black", "none").unwrap(),
+        );
+    let points = |i: usize| -> Vec<Point<T>> {
+        match &scene.shapes()[i].geometry {
+            Geometry::Path(path) => path
+                .commands()
+                .iter()
+                .filter_map(|command| match *command {
+                    PathCommand::MoveTo(point) | PathCommand::CubicTo(_, _, point) => Some(point),
+                    PathCommand::Close => None,
+                    _ => unreachable!(),
+                })
+                .collect(),
+            _ => unreachable!(),
+        }
+    };
+    // every curve ends where it started
+    for i in 0..4 {
+        let points = points(i);
+        let (first, last) = (points[0], points[points.len() - 1]);
+        assert!((first.x() - last.x()).abs() < 1e-9 && (first.y() - last.y()).abs() < 1e-9);
+    }
+    // the rose starts at the tip of its petal at 90 degrees, the deltoid has its cusps on the fixed circle
+    assert!(points(1)[0].x().abs() < 1e-9 && (points(1)[0].y() - 40.0).abs() < 1e-9);
+    let farthest = points(2)
+        .iter()
+        .map(|point| point.x().hypot(point.y()))
+        .fold(0.0, T::max);
+    assert!((farthest - 40.0).abs() < 1e-9);
+    // the cardioid reaches three times the radius of the fixed circle
+    let farthest = points(3)
+        .iter()
+        .map(|point| point.x().hypot(point.y()))
+        .fold(0.0, T::max);
+    assert!((farthest - 30.0).abs() < 1e-9);
+
+    let canvas = scene.render(canvas);
+    svg::save("./unit_tests/base_shapes/add_roulettes.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_add_splines() {
+    use super::Spline;
+    use crate::error::Error;
+    use crate::path::PathCommand;
+    use crate::scene::{Geometry, Scene};
+    let view_size = 100.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let config = Config::<T>::try_new(1.0, "black", "none").unwrap();
+    let points = vec![
+        point!(x: -80.0, y: 0.0),
+        point!(x: -40.0, y: 40.0),
+        point!(x: 0.0, y: 0.0),
+        point!(x: 0.0, y: 0.0),
+        point!(x: 40.0, y: -40.0),
+        point!(x: 80.0, y: 0.0),
+    ];
+    let scene = Scene::new()
+        .add_spline(points.clone(), Spline::Uniform, config.clone())
+        .add_spline(points.clone(), Spline::Centripetal, config.clone())
+        .add_smooth_loop(
+            points[..3].to_vec(),
+            Spline::Chordal,
+            Config::try_new(1.0, "black", "haldi").unwrap(),
+        )
+        .add_quadratic_bezier(
+            point!(x: -80.0, y: -80.0),
+            point!(x: -60.0, y: -40.0),
+            point!(x: -40.0, y: -80.0),
+            config.clone(),
+        )
+        .add_cubic_bezier(
+            point!(x: 40.0, y: 80.0),
+            point!(x: 50.0, y: 40.0),
+            point!(x: 70.0, y: 40.0),
+            point!(x: 80.0, y: 80.0),
+            config.clone(),
+        );
+    let commands = |i: usize| match &scene.shapes()[i].geometry {
+        Geometry::Path(path) => path.commands().to_vec(),
+        _ => unreachable!(),
+    };
+
+    // splines go through every point, a uniform one with tangents parallel to its neighbours' chord
+    let uniform = commands(0);
+    assert_eq!(uniform.len(), points.len());
+    for (command, point) in uniform.iter().skip(1).zip(points.iter().skip(1)) {
+        assert!(matches!(command, PathCommand::CubicTo(_, _, end) if end == point));
+    }
+    let expected = points[1] + (points[2] - points[0]) / 6.0;
+    match uniform[2] {
+        PathCommand::CubicTo(control, ..) => {
+            assert!(
+                (control.x() - expected.x()).abs() < 1e-9
+                    && (control.y() - expected.y()).abs() < 1e-9
+            )
+        }
+        _ => unreachable!(),
+    }
+    // the repeated point doesn't break the centripetal spline
+    assert!(commands(1).iter().all(|command| match command {
+        PathCommand::CubicTo(a, b, c) => [a, b, c]
+            .iter()
+            .all(|p| p.x().is_finite() && p.y().is_finite()),
+        _ => true,
+    }));
+    // the loop is smooth where it closes
+    let closed = commands(2);
+    assert!(matches!(closed.last(), Some(PathCommand::Close)));
+    match (&closed[1], &closed[3]) {
+        (PathCommand::CubicTo(after, ..), PathCommand::CubicTo(_, before, end)) => {
+            let (incoming, outgoing) = (*end - *before, *after - *end);
+            assert!((incoming.x() * outgoing.y() - incoming.y() * outgoing.x()).abs() < 1e-9);
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(
+        Scene::new()
+            .try_add_smooth_loop(points[..2].to_vec(), Spline::Uniform, config)
+            .err(),
+        Some(Error::TooFewVertices(2))
+    );
+    // the bounds of a Bezier curve are those of the curve and its stroke, not of its control points
+    let bounds: geo::Rect<T> = scene.shapes()[4].bounds().unwrap();
+    assert!((bounds.min().y - 49.5).abs() < 1e-9);
+
+    let canvas = scene.render(canvas);
+    svg::save("./unit_tests/base_shapes/add_splines.svg", &canvas).unwrap();
+}
@@ -1,7 +1,8 @@
 //! This file describes the `Leaves` trait
 //! A leaf is drawn on a canvas.
 //! Different leaf styles are supported.
-use crate::art_forms::base_shapes::{Config, BaseShapes};
+use crate::art_forms::base_shapes::{Config, BaseShapes, Placement};
+use crate::error::{or_panic, Error, Result};
 use derive_new::new;
 use geo::point;
 use geo::prelude::*;
@@ -46,7 +47,19 @@ where
         tip: Point<T>,
         leaf_style: LeafStyleDetailed<T>,
         config: Config<T>,
-    ) -> Self;
+    ) -> Self {
+        or_panic(self.try_add_single_leaf(start, end, tip, leaf_style, config))
+    }
+
+    /// Add a leaf, failing if a leaf with a point base doesn't start and end at the same point.
+    fn try_add_single_leaf(
+        self,
+        start: Point<T>,
+        end: Point<T>,
+        tip: Point<T>,
+        leaf_style: LeafStyleDetailed<T>,
+        config: Config<T>,
+    ) -> Result<Self>;
 
     // Add a linear leaf
     // The leaf points away from the origin as long as origin -> p -> q is counter-clockwise.
@@ -136,6 +149,7 @@ where
     }
 
     // Add circular leaves on an arc
+    #[allow(clippy::too_many_arguments)]
     fn add_circular_leaves_on_an_arc(
         self,
         radius: T,
@@ -146,26 +160,98 @@ where
         leaf_style: LeafStyle<T>,
         config: Config<T>,
     ) -> Self {
-        assert!(num_leaves > 0, "num_leaves must be positive");
+        or_panic(self.try_add_circular_leaves_on_an_arc(
+            radius, center, alpha, beta, num_leaves, leaf_style, config,
+        ))
+    }
+
+    /// Add circular leaves on an arc, failing if `num_leaves` is zero.
+    #[allow(clippy::too_many_arguments)]
+    fn try_add_circular_leaves_on_an_arc(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        beta: T,
+        num_leaves: usize,
+        leaf_style: LeafStyle<T>,
+        config: Config<T>,
+    ) -> Result<Self> {
+        self.try_add_circular_leaves_on_an_arc_with(
+            radius,
+            center,
+            alpha,
+            beta,
+            num_leaves,
+            leaf_style,
+            |_| config.clone(),
+        )
+    }
+
+    /// Add circular leaves on an arc, styled one by one by `style`.
+    /// The angle of a leaf is the middle of its arc, its point is on the circle at that angle.
+    #[allow(clippy::too_many_arguments)]
+    fn add_circular_leaves_on_an_arc_with<F>(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        beta: T,
+        num_leaves: usize,
+        leaf_style: LeafStyle<T>,
+        style: F,
+    ) -> Self
+    where
+        F: Fn(Placement<T>) -> Config<T>,
+    {
+        or_panic(self.try_add_circular_leaves_on_an_arc_with(
+            radius, center, alpha, beta, num_leaves, leaf_style, style,
+        ))
+    }
+
+    /// Add circular leaves on an arc styled one by one by `style`, failing if `num_leaves` is zero.
+    #[allow(clippy::too_many_arguments)]
+    fn try_add_circular_leaves_on_an_arc_with<F>(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        beta: T,
+        num_leaves: usize,
+        leaf_style: LeafStyle<T>,
+        style: F,
+    ) -> Result<Self>
+    where
+        F: Fn(Placement<T>) -> Config<T>,
+    {
+        if num_leaves == 0 {
+            return Err(Error::NoLeaves);
+        }
         let leaf_span = (beta - alpha) / T::from(num_leaves).unwrap();
-        let yantra = (0..num_leaves)
-            .map(|i| alpha + T::from(i).unwrap() * leaf_span)
-            .fold(self, |yantra, cur_alpha| {
+        Ok((0..num_leaves)
+            .map(|i| (i, alpha + T::from(i).unwrap() * leaf_span))
+            .fold(self, |yantra, (index, cur_alpha)| {
+                let angle = cur_alpha + leaf_span / T::from(2.0).unwrap();
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let point = center + point!(x: radius * cos, y: radius * sin);
                 yantra.add_single_circular_leaf(
                     radius,
                     center,
                     cur_alpha,
                     cur_alpha + leaf_span,
                     leaf_style.clone(),
-                    config.clone(),
+                    style(Placement {
+                        index,
+                        angle,
+                        point,
+                    }),
                 )
-            });
-
-        yantra
+            }))
     }
 
     // Add a circular leaf on a circle
     // Orientation is 1.0 for counterclockwise, -1.0 for clockwise.
+    #[allow(clippy::too_many_arguments)]
     fn add_circular_leaves(
         self,
         radius: T,
@@ -176,14 +262,90 @@ where
         leaf_style: LeafStyle<T>,
         config: Config<T>,
     ) -> Self {
-        self.add_circular_leaves_on_an_arc(
+        or_panic(self.try_add_circular_leaves(
             radius,
             center,
             alpha,
-            alpha + orientation * T::from(360.0).unwrap(),
             num_leaves,
+            orientation,
             leaf_style,
             config,
+        ))
+    }
+
+    /// Add circular leaves on a circle, failing if `num_leaves` is zero.
+    #[allow(clippy::too_many_arguments)]
+    fn try_add_circular_leaves(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        num_leaves: usize,
+        orientation: T,
+        leaf_style: LeafStyle<T>,
+        config: Config<T>,
+    ) -> Result<Self> {
+        self.try_add_circular_leaves_with(
+            radius,
+            center,
+            alpha,
+            num_leaves,
+            orientation,
+            leaf_style,
+            |_| config.clone(),
         )
     }
-}
\ No newline at end of file
+
+    /// Add circular leaves on a circle, styled one by one by `style`,
+    /// e.g. to alternate petal colours or sweep the hue around the circle.
+    #[allow(clippy::too_many_arguments)]
+    fn add_circular_leaves_with<F>(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        num_leaves: usize,
+        orientation: T,
+        leaf_style: LeafStyle<T>,
+        style: F,
+    ) -> Self
+    where
+        F: Fn(Placement<T>) -> Config<T>,
+    {
+        or_panic(self.try_add_circular_leaves_with(
+            radius,
+            center,
+            alpha,
+            num_leaves,
+            orientation,
+            leaf_style,
+            style,
+        ))
+    }
+
+    /// Add circular leaves on a circle styled one by one by `style`, failing if `num_leaves` is zero.
+    #[allow(clippy::too_many_arguments)]
+    fn try_add_circular_leaves_with<F>(
+        self,
+        radius: T,
+        center: Point<T>,
+        alpha: T,
+        num_leaves: usize,
+        orientation: T,
+        leaf_style: LeafStyle<T>,
+        style: F,
+    ) -> Result<Self>
+    where
+        F: Fn(Placement<T>) -> Config<T>,
+    {
+        self.try_add_circular_leaves_on_an_arc_with(
+            radius,
+            center,
+            alpha,
+            alpha + orientation * T::from(360.0).unwrap(),
+            num_leaves,
+            leaf_style,
+            style,
+        )
+    }
+}
@@ -1,6 +1,7 @@
 pub mod leaves_trait;
 pub use leaves_trait::{Leaves, LeafStyle, LeafStyleDetailed};
 pub mod leaves_impl;
+pub use leaves_impl::{leaf_outline, try_leaf_outline};
 
 #[cfg(test)]
 pub mod test_leaves;
\ No newline at end of file
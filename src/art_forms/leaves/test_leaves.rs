@@ -1,10 +1,10 @@
 use crate::art_forms::base_shapes::{BaseShapes, Config};
 use crate::art_forms::leaves::{LeafStyle, Leaves};
 
+use crate::canvas::SvgCanvas;
 use geo::point;
-use svg::Document;
 
-type Canvas = Document;
+type Canvas = SvgCanvas;
 type T = f64;
 
 #[test]
@@ -112,3 +112,147 @@ fn test_add_circular_leaves_on_a_circle() {
     // Save the image
     svg::save("./unit_tests/leaves/add_circular_leaves.svg", &canvas).unwrap();
 }
+
+#[test]
+fn test_add_circular_leaves_with_style() {
+    use crate::color::Color;
+    use crate::scene::Scene;
+    let center = point!(x: 0.0, y: 0.0);
+    let radius = 100.0;
+    let reniform_style = LeafStyle::<T>::new_reniform(25.0, 1.1, 12.0, 13.0);
+    // alternating petal colours, the first petal highlighted
+    let scene = Scene::new().add_circular_leaves_with(
+        radius,
+        center,
+        0.0,
+        16,
+        1.0,
+        reniform_style.clone(),
+        |placement| match (placement.index, placement.index % 2) {
+            (0, _) => Config::try_new(2.0, "black", "gold").unwrap(),
+            (_, 0) => Config::try_new(1.0, "none", "kumkum").unwrap(),
+            _ => Config::try_new(1.0, "none", "dadini").unwrap(),
+        },
+    );
+    let fills: Vec<String> = scene
+        .shapes()
+        .iter()
+        .map(|shape| shape.config.fill().svg_value())
+        .collect();
+    assert_eq!(fills.len(), 16);
+    assert_eq!(fills[0], "#ffd700");
+    assert_eq!(fills[1], "#fd9d97");
+    assert_eq!(fills[2], "#f33625");
+
+    // leaves are placed in the middle of their arc, going clockwise for a negative orientation
+    let scene = Scene::new().add_circular_leaves_on_an_arc_with(
+        radius,
+        center,
+        0.0,
+        -90.0,
+        3,
+        reniform_style.clone(),
+        |placement| {
+            assert!((placement.angle + 15.0 + 30.0 * placement.index as T).abs() < 1e-9);
+            let (sin, cos) = placement.angle.to_radians().sin_cos();
+            assert!((placement.point.x() - radius * cos).abs() < 1e-9);
+            assert!((placement.point.y() - radius * sin).abs() < 1e-9);
+            Config::try_new(1.0, "none", "none").unwrap()
+        },
+    );
+    assert_eq!(scene.shapes().len(), 3);
+
+    // a hue sweep around the circle
+    let view_size = 150.0;
+    let canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let canvas = canvas.add_circular_leaves_with(
+        radius,
+        center,
+        0.0,
+        24,
+        1.0,
+        reniform_style,
+        |placement| Config::try_new(0.5, "black", Color::hsl(placement.angle, 0.8, 0.6)).unwrap(),
+    );
+    // the first leaf is centered at 7.5 degrees
+    let red = Color::hsl(7.5, 0.8, 0.6);
+    assert!(canvas.to_string().contains(&red.to_string()));
+    svg::save("./unit_tests/leaves/add_circular_leaves_with.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_point_base_must_be_a_point() {
+    use crate::art_forms::leaves::LeafStyleDetailed;
+    use crate::error::Error;
+    let config = Config::<T>::try_new(1.0, "black", "none").unwrap();
+    let style = LeafStyleDetailed::Point(10.0, 5.0, 5.0, 2.0);
+    let result = Canvas::new().try_add_single_leaf(
+        point!(x: 0.0, y: 0.0),
+        point!(x: 1.0, y: 0.0),
+        point!(x: 0.0, y: 20.0),
+        style.clone(),
+        config.clone(),
+    );
+    assert_eq!(result.err(), Some(Error::PointBaseNotAPoint));
+    let result = Canvas::new().try_add_single_leaf(
+        point!(x: 0.0, y: 0.0),
+        point!(x: 0.0, y: 0.0),
+        point!(x: 0.0, y: 20.0),
+        style,
+        config,
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_circular_base_needs_a_valid_radius() {
+    use crate::art_forms::leaves::LeafStyleDetailed;
+    use crate::error::Error;
+    let config = Config::<T>::try_new(1.0, "black", "none").unwrap();
+    let leaf = |radius: T| {
+        Canvas::new().try_add_single_leaf(
+            point!(x: -5.0, y: 0.0),
+            point!(x: 5.0, y: 0.0),
+            point!(x: 0.0, y: 20.0),
+            LeafStyleDetailed::Circular(10.0, 5.0, 5.0, 2.0, radius),
+            config.clone(),
+        )
+    };
+    for radius in [-1.0, T::NAN, T::INFINITY] {
+        assert_eq!(leaf(radius).err(), Some(Error::InvalidRadius));
+    }
+    assert!(leaf(10.0).is_ok());
+}
+
+#[test]
+fn test_leaves_need_at_least_one_leaf() {
+    use crate::error::Error;
+    let config = Config::<T>::try_new(1.0, "black", "none").unwrap();
+    let style = LeafStyle::<T>::new_reniform(25.0, 1.1, 12.0, 13.0);
+    let center = point!(x: 0.0, y: 0.0);
+    let result = Canvas::new().try_add_circular_leaves(
+        100.0,
+        center,
+        0.0,
+        0,
+        1.0,
+        style.clone(),
+        config.clone(),
+    );
+    assert_eq!(result.err(), Some(Error::NoLeaves));
+    let result = Canvas::new().try_add_circular_leaves_on_an_arc(
+        100.0,
+        center,
+        0.0,
+        90.0,
+        0,
+        style.clone(),
+        config.clone(),
+    );
+    assert_eq!(result.err(), Some(Error::NoLeaves));
+    let result = Canvas::new().try_add_circular_leaves(100.0, center, 0.0, 4, 1.0, style, config);
+    assert!(result.is_ok());
+}
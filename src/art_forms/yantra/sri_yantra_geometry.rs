@@ -1,11 +1,12 @@
 //! A library to construct Shri Yantra like objects see [Wiki](https://en.wikipedia.org/wiki/Sri_Yantra).
 
 // use geo::prelude::*;
+use crate::error::{or_panic, Error, Result};
 use crate::utils::intersection; // {dist, intersection, polar_point};
 use geo::prelude::*;
 use geo::{CoordFloat, GeoFloat, Point};
 use numeric_literals::replace_numeric_literals;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 #[derive(Clone)]
 /// # `ShriYantra` struct details.
@@ -15,8 +16,8 @@ use std::collections::HashMap;
 ///
 /// - The paths look like lotuses with petals 14 + 10 + 10 + 8 + 1 = 43.
 /// - Shri yantra has some triple intersection and concurrency requirements,
-///     after which it still has four degrees of freedom (see [Chiodo](https://doi.org/10.5802/crmath.163))
-///     which one can think of as five parameters satisfying one equation.
+///   after which it still has four degrees of freedom (see [Chiodo](https://doi.org/10.5802/crmath.163))
+///   which one can think of as five parameters satisfying one equation.
 /// - The parameters here are named as in this paper by [Fonseca](http://dx.doi.org/10.1016/0048-721x(86)90004-7).
 pub struct ShriYantra<T: CoordFloat = f64> {
     /// Radius of the inscribing circle.
@@ -34,11 +35,12 @@ pub struct ShriYantra<T: CoordFloat = f64> {
     /// Length of `XI` as in [Fonseca](http://dx.doi.org/10.1016/0048-721x(86)90004-7).
     pub param_i: T,
     /// Map that takes a coordinate name to its point value.
-    coords: HashMap<CoordName, Point<T>>,
+    coords: BTreeMap<CoordName, Point<T>>,
 }
 
-#[derive(Eq, Hash, PartialEq, Clone, Copy)]
+#[derive(Eq, Hash, PartialEq, Ord, PartialOrd, Clone, Copy, Debug)]
 /// A dictionary of points in the Sri Yantra
+#[allow(clippy::upper_case_acronyms)]
 enum CoordName {
     // U denote an up triangle, D a down triangle,
     // 1, 2, .. denote their sizes starting from the largest point
@@ -123,6 +125,9 @@ enum CoordName {
     BINDU,
 }
 
+/// The nine paths of a Sri Yantra, see [`ShriYantra::try_paths`].
+pub type SriPaths<T> = [Vec<Point<T>>; 9];
+
 #[replace_numeric_literals(T::from(literal).unwrap())]
 impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
 
@@ -141,24 +146,44 @@ impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
         // Some optimal values of (a,f):
         // (a, f) = (5.0, 26.5), (6, 26.1), (6.2, 26.0)
         ShriYantra {
-            radius: radius,
-            center: center,
+            radius,
+            center,
             param_a: param_a.unwrap_or(diameter * 5.0 / 48.0),
             param_c: param_c.unwrap_or(diameter * 17.0 / 48.0),
             param_f: param_f.unwrap_or(diameter * 26.5 / 48.0),
             param_g: param_g.unwrap_or(diameter * 30.0 / 48.0),
             param_i: param_i.unwrap_or(diameter * 42.0 / 48.0),
-            coords: HashMap::<CoordName, Point<T>>::new(),
+            coords: BTreeMap::<CoordName, Point<T>>::new(),
         }
     }
 
-    /// Get all contructed points a `SriYantra`.
+    /// Get all contructed points a `SriYantra`, in the order of their names.
     pub fn get_all_points(&mut self) -> Vec<Point<T>> {
         self.coords.values().cloned().collect()
     }
 
 
+    /// The nine paths drawn by `add_sri`, from the outermost in,
+    /// failing if a point they go through hasn't been constructed.
+    pub fn try_paths(&self) -> Result<SriPaths<T>> {
+        Ok([
+            self.try_first_outer_path()?,
+            self.try_first_inner_path()?,
+            self.try_second_outer_path()?,
+            self.try_second_inner_path()?,
+            self.try_third_outer_path()?,
+            self.try_third_inner_path()?,
+            self.try_fourth_outer_path()?,
+            self.try_fourth_inner_path()?,
+            self.try_fifth_outer_path()?,
+        ])
+    }
+
     pub fn first_outer_path(&self) -> Vec<Point<T>> {
+        or_panic(self.try_first_outer_path())
+    }
+
+    pub fn try_first_outer_path(&self) -> Result<Vec<Point<T>>> {
         type C = CoordName;
         let west_path = vec![
             C::SWH,
@@ -179,12 +204,20 @@ impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
     }
 
     pub fn first_inner_path(&self) -> Vec<Point<T>> {
+        or_panic(self.try_first_inner_path())
+    }
+
+    pub fn try_first_inner_path(&self) -> Result<Vec<Point<T>>> {
         type C = CoordName;
         let west_path = vec![C::SWH, C::WH1, C::NWH];
         self.reflected_and_closed_path(west_path, None, None)
     }
 
     pub fn second_outer_path(&self) -> Vec<Point<T>> {
+        or_panic(self.try_second_outer_path())
+    }
+
+    pub fn try_second_outer_path(&self) -> Result<Vec<Point<T>>> {
         type C = CoordName;
         let west_path = vec![
             C::WI3,
@@ -201,12 +234,20 @@ impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
     }
 
     pub fn second_inner_path(&self) -> Vec<Point<T>> {
+        or_panic(self.try_second_inner_path())
+    }
+
+    pub fn try_second_inner_path(&self) -> Result<Vec<Point<T>>> {
         type C = CoordName;
         let west_path = vec![C::WI3, C::WI2, C::WI1];
         self.reflected_and_closed_path(west_path, None, None)
     }
 
     pub fn third_outer_path(&self) -> Vec<Point<T>> {
+        or_panic(self.try_third_outer_path())
+    }
+
+    pub fn try_third_outer_path(&self) -> Result<Vec<Point<T>>> {
         type C = CoordName;
         let west_path = vec![
             C::SWF,
@@ -223,24 +264,40 @@ impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
     }
 
     pub fn third_inner_path(&self) -> Vec<Point<T>> {
+        or_panic(self.try_third_inner_path())
+    }
+
+    pub fn try_third_inner_path(&self) -> Result<Vec<Point<T>>> {
         type C = CoordName;
         let west_path = vec![C::SWF, C::WK, C::NWF];
         self.reflected_and_closed_path(west_path, None, None)
     }
 
     pub fn fourth_outer_path(&self) -> Vec<Point<T>> {
+        or_panic(self.try_fourth_outer_path())
+    }
+
+    pub fn try_fourth_outer_path(&self) -> Result<Vec<Point<T>>> {
         type C = CoordName;
         let west_path = vec![C::WJ3, C::SWG4, C::WJ2, C::DL4, C::WG, C::NWG4, C::WJ1];
         self.reflected_and_closed_path(west_path, Some(C::DM1), Some(C::UM1))
     }
 
     pub fn fourth_inner_path(&self) -> Vec<Point<T>> {
+        or_panic(self.try_fourth_inner_path())
+    }
+
+    pub fn try_fourth_inner_path(&self) -> Result<Vec<Point<T>>> {
         type C = CoordName;
         let west_path = vec![C::WJ3, C::WJ2, C::WJ1];
         self.reflected_and_closed_path(west_path, None, None)
     }
 
     pub fn fifth_outer_path(&self) -> Vec<Point<T>> {
+        or_panic(self.try_fifth_outer_path())
+    }
+
+    pub fn try_fifth_outer_path(&self) -> Result<Vec<Point<T>>> {
         type C = CoordName;
         let west_path = vec![C::WG];
         self.reflected_and_closed_path(west_path, None, Some(C::UM3))
@@ -252,11 +309,11 @@ impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
         west_path_coords: Vec<CoordName>,
         top_coord: Option<CoordName>,
         bottom_coord: Option<CoordName>,
-    ) -> Vec<Point<T>> {
+    ) -> Result<Vec<Point<T>>> {
         let mut west_path: Vec<Point<T>> = west_path_coords
             .into_iter()
             .map(|coord| self.get_point(coord))
-            .collect();
+            .collect::<Result<_>>()?;
         let reflect_y = AffineTransform::scale(-1.0, 1.0, self.center);
         let mut east_path: Vec<Point<T>> = west_path
             .clone()
@@ -265,14 +322,14 @@ impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
             .rev()
             .collect();
         if let Some(coord) = top_coord {
-            west_path.push(self.get_point(coord));
+            west_path.push(self.get_point(coord)?);
         };
         if let Some(coord) = bottom_coord {
-            east_path.push(self.get_point(coord));
+            east_path.push(self.get_point(coord)?);
         };
 
         west_path.append(&mut east_path);
-        west_path
+        Ok(west_path)
     }
 
     #[allow(dead_code)]
@@ -284,8 +341,8 @@ impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
         self.insert(CoordName::BINDU, self.center)
     }
 
-    fn mid_point(&self, coord1: CoordName, coord2: CoordName) -> Point<T> {
-        (self.get_point(coord1) + self.get_point(coord2)) / 2.0
+    fn mid_point(&self, coord1: CoordName, coord2: CoordName) -> Result<Point<T>> {
+        Ok((self.get_point(coord1)? + self.get_point(coord2)?) / 2.0)
     }
 
     fn interpolate_and_intersect_with_chord(
@@ -293,12 +350,12 @@ impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
         chord_point: CoordName,
         coord1: CoordName,
         coord2: CoordName,
-    ) -> Point<T> {
+    ) -> Result<Point<T>> {
         #[allow(non_snake_case)]
         let INTERPOLATION_FACTOR = 25.0;
-        let point1 = self.get_point(coord1);
-        let point2 = self.get_point(coord2);
-        let chord_point = self.get_point(chord_point);
+        let point1 = self.get_point(coord1)?;
+        let point2 = self.get_point(coord2)?;
+        let chord_point = self.get_point(chord_point)?;
         let chord_end = Point::new(
             self.radius * (chord_point.y() / self.radius).acos().sin(),
             chord_point.y(),
@@ -317,13 +374,13 @@ impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
         coord2: CoordName,
         coord3: CoordName,
         coord4: CoordName,
-    ) -> Point<T> {
+    ) -> Result<Point<T>> {
         #[allow(non_snake_case)]
         let INTERPOLATION_FACTOR = 25.0;
-        let point1 = self.get_point(coord1);
-        let point2 = self.get_point(coord2);
-        let point3 = self.get_point(coord3);
-        let point4 = self.get_point(coord4);
+        let point1 = self.get_point(coord1)?;
+        let point2 = self.get_point(coord2)?;
+        let point3 = self.get_point(coord3)?;
+        let point4 = self.get_point(coord4)?;
         // let points = coords.iter().map(|coord| self.get_point(*coord)).collect();
         intersection(
             point1 + (point1 - point2) * INTERPOLATION_FACTOR,
@@ -333,8 +390,11 @@ impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
         )
     }
 
-    fn get_point(&self, coord_name: CoordName) -> Point<T> {
-        *self.coords.get(&coord_name).unwrap()
+    fn get_point(&self, coord_name: CoordName) -> Result<Point<T>> {
+        self.coords
+            .get(&coord_name)
+            .copied()
+            .ok_or_else(|| Error::MissingPoint(format!("{:?}", coord_name)))
     }
 
     fn insert(&mut self, coord_name: CoordName, point: Point<T>) {
@@ -369,7 +429,14 @@ impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
         self.center + (point - self.center).affine_transform(transform)
     }
 
+    /// Construct all points, panicking if two lines that should cross don't.
     pub fn construct_all_points(&mut self) {
+        or_panic(self.try_construct_all_points())
+    }
+
+    /// Construct all points, failing if two lines that should cross don't,
+    /// as happens for some choices of the parameters.
+    pub fn try_construct_all_points(&mut self) -> Result<()> {
         type C = CoordName;
 
         // Get the first up and down triangles and their intersections
@@ -386,35 +453,35 @@ impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
         self.insert(C::DM1, Point::new(0, doffset_y));
         self.insert_east_west(C::DL1, C::DR1, Point::new(-doffset_x, doffset_y));
 
-        let nwg_1 = self.interpolate_and_intersect(C::UT1, C::UL1, C::DL1, C::DR1);
+        let nwg_1 = self.interpolate_and_intersect(C::UT1, C::UL1, C::DL1, C::DR1)?;
         self.insert_east_west(C::NWG1, C::NEG1, nwg_1);
 
-        let swg_1 = self.interpolate_and_intersect(C::DT1, C::DL1, C::UL1, C::UR1);
+        let swg_1 = self.interpolate_and_intersect(C::DT1, C::DL1, C::UL1, C::UR1)?;
         self.insert_east_west(C::SWG1, C::SEG1, swg_1);
 
         // Get the double intersection on the first path
-        let wh = self.interpolate_and_intersect(C::UT1, C::UL1, C::DL1, C::DT1);
+        let wh = self.interpolate_and_intersect(C::UT1, C::UL1, C::DL1, C::DT1)?;
         self.insert_east_west(C::WH1, C::EH1, wh);
 
         // Get the tip of the second down triangle using param i
         self.insert(C::DT2, Point::new(0.0, self.radius - self.param_i));
 
         // Get the southernmost triple points on the first and the second paths
-        let swg_3 = self.interpolate_and_intersect(C::NWG1, C::DT2, C::UL1, C::UR1);
+        let swg_3 = self.interpolate_and_intersect(C::NWG1, C::DT2, C::UL1, C::UR1)?;
         self.insert_east_west(C::SWG3, C::SEG3, swg_3);
 
-        let swg_2 = self.interpolate_and_intersect(C::DM1, C::SWG3, C::DL1, C::DT1);
+        let swg_2 = self.interpolate_and_intersect(C::DM1, C::SWG3, C::DL1, C::DT1)?;
         self.insert_east_west(C::SWG2, C::SEG2, swg_2);
 
         // Get the base of the fourth up triangle
-        let ul_4 = self.interpolate_and_intersect_with_chord(C::DT2, C::DM1, C::SWG3);
+        let ul_4 = self.interpolate_and_intersect_with_chord(C::DT2, C::DM1, C::SWG3)?;
         self.insert_east_west(C::UL4, C::UR4, ul_4);
 
         // Use param a to get the ip of the second up triangle
         self.insert(C::UT2, Point::new(0.0, self.radius - self.param_a));
 
         // Get the remaining triple point on the second path
-        let nwg_3 = self.interpolate_and_intersect(C::SWG1, C::UT2, C::DL1, C::DR1);
+        let nwg_3 = self.interpolate_and_intersect(C::SWG1, C::UT2, C::DL1, C::DR1)?;
         self.insert_east_west(C::NWG3, C::NEG3, nwg_3);
 
         // Use param f to get UM3
@@ -422,80 +489,81 @@ impl<T: CoordFloat + GeoFloat> ShriYantra<T> {
         self.insert(C::UM3, um_3);
 
         // Get the remaining triple intersection on the first triangle
-        let nwg_2 = self.interpolate_and_intersect(C::UM3, C::NWG3, C::UL1, C::UT1);
+        let nwg_2 = self.interpolate_and_intersect(C::UM3, C::NWG3, C::UL1, C::UT1)?;
         self.insert_east_west(C::NWG2, C::NEG2, nwg_2);
 
         // Get remaining points on the first path
-        let dl_5 = self.interpolate_and_intersect_with_chord(C::UT2, C::UM3, C::NWG3);
+        let dl_5 = self.interpolate_and_intersect_with_chord(C::UT2, C::UM3, C::NWG3)?;
         self.insert_east_west(C::DL5, C::DR5, dl_5);
 
-        let ul_2 = self.interpolate_and_intersect(C::UT2, C::SWG1, C::SWG2, C::SEG2);
+        let ul_2 = self.interpolate_and_intersect(C::UT2, C::SWG1, C::SWG2, C::SEG2)?;
         self.insert_east_west(C::UL2, C::UR2, ul_2);
 
-        let dl_2 = self.interpolate_and_intersect(C::DT2, C::NWG1, C::NWG2, C::NEG2);
+        let dl_2 = self.interpolate_and_intersect(C::DT2, C::NWG1, C::NWG2, C::NEG2)?;
         self.insert_east_west(C::DL2, C::DR2, dl_2);
 
-        let nwh = self.interpolate_and_intersect_with_chord(C::UT2, C::UT1, C::UL1);
+        let nwh = self.interpolate_and_intersect_with_chord(C::UT2, C::UT1, C::UL1)?;
         self.insert_east_west(C::NWH, C::NEH, nwh);
 
-        let swh = self.interpolate_and_intersect_with_chord(C::DT2, C::DT1, C::DL1);
+        let swh = self.interpolate_and_intersect_with_chord(C::DT2, C::DT1, C::DL1)?;
         self.insert_east_west(C::SWH, C::SEH, swh);
 
         // Get remaining points on the second path
-        let wi_2 = self.interpolate_and_intersect(C::UT2, C::SWG1, C::DT2, C::NWG1);
+        let wi_2 = self.interpolate_and_intersect(C::UT2, C::SWG1, C::DT2, C::NWG1)?;
         self.insert_east_west(C::WI2, C::EI2, wi_2);
 
-        let wi_1 = self.interpolate_and_intersect(C::UT2, C::NWG3, C::NWG2, C::NEG2);
+        let wi_1 = self.interpolate_and_intersect(C::UT2, C::NWG3, C::NWG2, C::NEG2)?;
         self.insert_east_west(C::WI1, C::EI1, wi_1);
 
-        let wi_3 = self.interpolate_and_intersect(C::DT2, C::SWG3, C::SWG2, C::SEG2);
+        let wi_3 = self.interpolate_and_intersect(C::DT2, C::SWG3, C::SWG2, C::SEG2)?;
         self.insert_east_west(C::WI3, C::EI3, wi_3);
 
         //  Get the third up triangle
-        self.insert(C::UT3, self.mid_point(C::WI1, C::EI1));
+        self.insert(C::UT3, self.mid_point(C::WI1, C::EI1)?);
 
-        let ul_3 = self.interpolate_and_intersect_with_chord(C::UM3, C::NWG1, C::DT2);
+        let ul_3 = self.interpolate_and_intersect_with_chord(C::UM3, C::NWG1, C::DT2)?;
         self.insert_east_west(C::UL3, C::UR3, ul_3);
 
         // Get the last triple points and the third down triangle
-        let wg = self.interpolate_and_intersect(C::UM3, C::NWG3, C::DM1, C::SWG3);
+        let wg = self.interpolate_and_intersect(C::UM3, C::NWG3, C::DM1, C::SWG3)?;
         self.insert_east_west(C::WG, C::EG, wg);
 
-        let swg_4 = self.interpolate_and_intersect_with_chord(C::UM3, C::DM1, C::SWG2);
+        let swg_4 = self.interpolate_and_intersect_with_chord(C::UM3, C::DM1, C::SWG2)?;
         self.insert_east_west(C::SWG4, C::SEG4, swg_4);
 
-        self.insert(C::DT3, self.mid_point(C::WI3, C::EI3));
-        let dl_3 = self.interpolate_and_intersect(C::DT3, C::SWG4, C::UT2, C::SWG1);
+        self.insert(C::DT3, self.mid_point(C::WI3, C::EI3)?);
+        let dl_3 = self.interpolate_and_intersect(C::DT3, C::SWG4, C::UT2, C::SWG1)?;
         self.insert_east_west(C::DL3, C::DR3, dl_3);
 
-        let nwg_4 = self.interpolate_and_intersect(C::UT3, C::UL3, C::DL3, C::DR3);
+        let nwg_4 = self.interpolate_and_intersect(C::UT3, C::UL3, C::DL3, C::DR3)?;
         self.insert_east_west(C::NWG4, C::NEG4, nwg_4);
 
         // Get the remaining double points on the third path
-        let nwf = self.interpolate_and_intersect_with_chord(C::DM1, C::UT3, C::UL3);
+        let nwf = self.interpolate_and_intersect_with_chord(C::DM1, C::UT3, C::UL3)?;
         self.insert_east_west(C::NWF, C::NEF, nwf);
 
-        let swf = self.interpolate_and_intersect_with_chord(C::UM1, C::DT3, C::DL3);
+        let swf = self.interpolate_and_intersect_with_chord(C::UM1, C::DT3, C::DL3)?;
         self.insert_east_west(C::SWF, C::SEF, swf);
 
-        let wk = self.interpolate_and_intersect(C::UT3, C::UL3, C::DT3, C::DL3);
+        let wk = self.interpolate_and_intersect(C::UT3, C::UL3, C::DT3, C::DL3)?;
         self.insert_east_west(C::WK, C::EK, wk);
 
         // Get the fourth down triangle
-        let dl_4 = self.interpolate_and_intersect_with_chord(C::EG, C::UT3, C::UL3);
+        let dl_4 = self.interpolate_and_intersect_with_chord(C::EG, C::UT3, C::UL3)?;
         self.insert_east_west(C::DL4, C::DR4, dl_4);
 
         // Get the remining points on the fourth path
-        let wj_1 = self.interpolate_and_intersect_with_chord(C::DL3, C::DM1, C::SWG2);
+        let wj_1 = self.interpolate_and_intersect_with_chord(C::DL3, C::DM1, C::SWG2)?;
         self.insert_east_west(C::WJ1, C::EJ1, wj_1);
 
-        let wj_2 = self.interpolate_and_intersect(C::UM1, C::DL4, C::DM1, C::SWG2);
+        let wj_2 = self.interpolate_and_intersect(C::UM1, C::DL4, C::DM1, C::SWG2)?;
         self.insert_east_west(C::WJ2, C::EJ2, wj_2);
 
-        let wj_3 = self.interpolate_and_intersect_with_chord(C::UM3, C::UM1, C::DL4);
+        let wj_3 = self.interpolate_and_intersect_with_chord(C::UM3, C::UM1, C::DL4)?;
         self.insert_east_west(C::WJ3, C::EJ3, wj_3);
 
         // Finally, set the bindu
         self.add_bindu();
+        Ok(())
     }
 }
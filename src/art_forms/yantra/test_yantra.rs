@@ -2,10 +2,10 @@
 use crate::art_forms::base_shapes::{BaseShapes, Config};
 
 use crate::art_forms::yantra::sri_yantra_geometry::ShriYantra;
+use crate::canvas::SvgCanvas;
 use geo::point;
-use svg::Document;
 
-type Canvas = Document;
+type Canvas = SvgCanvas;
 type T = f64;
 
 #[test]
@@ -93,3 +93,16 @@ fn test_sri_canvas() {
 
     svg::save("./unit_tests/yantra/add_sri.svg", &canvas).unwrap();
 }
+
+#[test]
+fn test_sri_errors() {
+    use crate::error::Error;
+    let center = point!(x: 0.0, y: 0.0);
+    let sri = ShriYantra::<T>::new(100.0, center, None, None, None, None, None);
+    // paths go through points that are only known once constructed
+    assert_eq!(sri.try_paths(), Err(Error::MissingPoint("SWH".to_string())));
+    assert_eq!(
+        Error::MissingPoint("SWH".to_string()).to_string(),
+        "point SWH has not been constructed"
+    );
+}
@@ -3,6 +3,7 @@
 
 use crate::art_forms::base_shapes::{BaseShapes, Config};
 use crate::art_forms::leaves::Leaves;
+use crate::error::{or_panic, Result};
 use geo::Point;
 use geo_types::CoordFloat;
 
@@ -12,5 +13,10 @@ where
     Self: Sized,
     T: CoordFloat,
 {
-    fn add_sri(self, radius: T, center: Point<T>, config: [Config<T>; 9]) -> Self;
+    fn add_sri(self, radius: T, center: Point<T>, config: [Config<T>; 9]) -> Self {
+        or_panic(self.try_add_sri(radius, center, config))
+    }
+
+    /// Add a Sri Yantra, failing if its construction doesn't go through.
+    fn try_add_sri(self, radius: T, center: Point<T>, config: [Config<T>; 9]) -> Result<Self>;
 }
@@ -0,0 +1,31 @@
+//! This file describes the `Backend` trait
+//! A backend is what the art forms are drawn on, like an svg document or a raster image.
+//! Art forms only use the primitives of this trait, so every backend supports every art form.
+use geo::Point;
+use geo_types::CoordFloat;
+
+use crate::art_forms::base_shapes::Config;
+use crate::path::Path;
+use crate::scene::{Geometry, Shape};
+
+/// `Self` is consumed everywhere in order to enable a builder pattern API.
+pub trait Backend<T: CoordFloat>
+where
+    Self: Sized,
+{
+    /// Draw a circle styled by `config`.
+    fn draw_circle(self, radius: T, center: Point<T>, config: Config<T>) -> Self;
+
+    /// Draw a path styled by `config`.
+    fn draw_path(self, path: Path<T>, config: Config<T>) -> Self;
+
+    /// Draw a shape of a [`Scene`](crate::scene::Scene).
+    /// Backends that can label their content keep the id of the shape.
+    fn draw_shape(self, shape: &Shape<T>) -> Self {
+        let config = shape.config.clone();
+        match &shape.geometry {
+            Geometry::Circle(radius, center) => self.draw_circle(*radius, *center, config),
+            Geometry::Path(path) => self.draw_path(path.clone(), config),
+        }
+    }
+}
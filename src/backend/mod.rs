@@ -0,0 +1,9 @@
+pub mod backend_trait;
+pub use backend_trait::Backend;
+pub mod raster_backend;
+pub use raster_backend::RasterCanvas;
+mod png;
+pub mod svg_backend;
+
+#[cfg(test)]
+pub mod test_backend;
@@ -0,0 +1,656 @@
+//! An in-memory raster backend.
+//! Shapes are flattened to polygons and filled with anti-aliasing, strokes are built
+//! from polygons for their segments, joins and caps,
+//! the result can be written as a PNG file without any svg renderer.
+use geo::{AffineTransform, Coord, Point, Rect};
+use geo_types::CoordFloat;
+use std::io;
+
+use super::backend_trait::Backend;
+use super::png::encode_rgba;
+use crate::art_forms::base_shapes::{Config, FillRule, LineCap, LineJoin};
+use crate::color::Color;
+use crate::error::{or_panic, Error, Result};
+use crate::paint::{GradientStop, Paint};
+use crate::path::segment::{path_sub_paths, Segment, SubPath};
+use crate::path::Path;
+
+/// Number of sub-scanlines per pixel row used for anti-aliasing.
+const SUB_SCANLINES: usize = 4;
+
+/// Straight RGBA components of a colour.
+fn channels(color: &Color) -> [f64; 4] {
+    [color.red, color.green, color.blue, color.alpha]
+}
+
+/// Twice the signed area of a polygon.
+fn signed_area(polygon: &[Coord]) -> f64 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(p, q)| p.x * q.y - q.x * p.y)
+        .sum()
+}
+
+/// A polygon approximating a circle with at most `max_step` pixels per edge.
+fn circle_polygon(center: Coord, radius: f64, max_step: f64) -> Vec<Coord> {
+    let n = ((2.0 * std::f64::consts::PI * radius / max_step).ceil() as usize).clamp(8, 1440);
+    (0..n)
+        .map(|i| {
+            let (sin, cos) = (2.0 * std::f64::consts::PI * i as f64 / n as f64).sin_cos();
+            center + Coord { x: cos, y: sin } * radius
+        })
+        .collect()
+}
+
+/// Points along a sub-path, in pixel coordinates.
+fn flatten(sub_path: &SubPath) -> Vec<Coord> {
+    let mut points = match sub_path.segments.first() {
+        Some(segment) => vec![segment.start()],
+        None => return vec![],
+    };
+    for segment in sub_path.segments.iter() {
+        let n = match segment {
+            Segment::Line(..) => 1,
+            Segment::Quadratic(p0, p1, p2) => {
+                let length = (*p1 - *p0).magnitude() + (*p2 - *p1).magnitude();
+                ((length / 2.0).ceil() as usize).clamp(2, 256)
+            }
+            Segment::Cubic(p0, p1, p2, p3) => {
+                let length =
+                    (*p1 - *p0).magnitude() + (*p2 - *p1).magnitude() + (*p3 - *p2).magnitude();
+                ((length / 2.0).ceil() as usize).clamp(2, 256)
+            }
+        };
+        points.extend((1..=n).map(|i| segment.point_at(i as f64 / n as f64)));
+    }
+    points
+}
+
+trait Magnitude {
+    fn magnitude(self) -> f64;
+}
+
+impl Magnitude for Coord {
+    fn magnitude(self) -> f64 {
+        self.x.hypot(self.y)
+    }
+}
+
+/// Straight RGBA colour of a paint at a point in pixel coordinates.
+type PaintSource = Box<dyn Fn(Coord) -> [f64; 4]>;
+
+/// Colour at `t` along gradient stops, padded with the end colours.
+fn gradient_color(stops: &[(f64, [f64; 4])], t: f64) -> [f64; 4] {
+    let after = stops.iter().position(|(offset, _)| *offset > t);
+    match after {
+        Some(0) => stops[0].1,
+        None => stops[stops.len() - 1].1,
+        Some(index) => {
+            let ((start, from), (end, to)) = (stops[index - 1], stops[index]);
+            let s = (t - start) / (end - start);
+            [0, 1, 2, 3].map(|channel| from[channel] * (1.0 - s) + to[channel] * s)
+        }
+    }
+}
+
+/// Parameter of a radial gradient at `point`: the circles grow from the focus (`0`)
+/// to the circle around `center` (`1`).
+fn radial_parameter(point: Coord, center: Coord, focus: Coord, radius: f64) -> f64 {
+    let (q, e) = (point - focus, center - focus);
+    let dot = |u: Coord, v: Coord| u.x * v.x + u.y * v.y;
+    // |q - t e| = t radius
+    let (a, b, c) = (dot(e, e) - radius * radius, -2.0 * dot(q, e), dot(q, q));
+    match a.abs() < 1e-12 {
+        true => c / -b,
+        false => {
+            let sqrt = (b * b - 4.0 * a * c).max(0.0).sqrt();
+            ((-b + sqrt) / (2.0 * a)).max((-b - sqrt) / (2.0 * a))
+        }
+    }
+}
+
+/// Caps, joins and width of a stroke, lengths in pixels.
+struct StrokeStyle {
+    width: f64,
+    cap: LineCap,
+    join: LineJoin,
+    miter_limit: f64,
+}
+
+/// Split a line into dashes, following svg `stroke-dasharray` and `stroke-dashoffset`.
+/// Returns `None` for a pattern that draws a solid line.
+fn dashes(points: &[Coord], closed: bool, pattern: &[f64], offset: f64) -> Option<Vec<Vec<Coord>>> {
+    let mut pattern = pattern.to_vec();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_within(..);
+    }
+    let total: f64 = pattern.iter().sum();
+    if pattern.is_empty() || total <= 0.0 || pattern.iter().any(|length| *length < 0.0) {
+        return None;
+    }
+    // Find where in the pattern the line starts
+    let (mut index, mut remaining) = (0, pattern[0]);
+    let mut offset = offset.rem_euclid(total);
+    while offset > 0.0 {
+        match offset >= remaining {
+            true => {
+                offset -= remaining;
+                index = (index + 1) % pattern.len();
+                remaining = pattern[index];
+            }
+            false => {
+                remaining -= offset;
+                offset = 0.0;
+            }
+        }
+    }
+
+    let closing = match closed {
+        true => points.first().zip(points.last()),
+        false => None,
+    };
+    let edges = points
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .chain(closing.map(|(first, last)| (*last, *first)));
+    let mut dashes = vec![];
+    let mut current: Vec<Coord> = match (index % 2, points.first()) {
+        (0, Some(start)) => vec![*start],
+        _ => vec![],
+    };
+    for (mut p, q) in edges {
+        loop {
+            let length = (q - p).magnitude();
+            if length <= remaining {
+                remaining -= length;
+                if index % 2 == 0 {
+                    current.push(q);
+                }
+                break;
+            }
+            p = p + (q - p) * (remaining / length);
+            match index % 2 {
+                0 => {
+                    current.push(p);
+                    dashes.push(std::mem::take(&mut current));
+                }
+                _ => current = vec![p],
+            }
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+    }
+    if index % 2 == 0 && !current.is_empty() {
+        dashes.push(current);
+    }
+    Some(dashes)
+}
+
+/// Polygons covering a stroke along the points.
+fn stroke_polygons(points: &[Coord], closed: bool, style: &StrokeStyle) -> Vec<Vec<Coord>> {
+    let half = style.width / 2.0;
+    let closing = match closed {
+        true => points.first().zip(points.last()),
+        false => None,
+    };
+    let edges: Vec<(Coord, Coord)> = points
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .chain(closing.map(|(first, last)| (*last, *first)))
+        .filter(|(p, q)| p != q)
+        .collect();
+    let normal = |(p, q): &(Coord, Coord)| {
+        let direction = *q - *p;
+        Coord {
+            x: -direction.y,
+            y: direction.x,
+        } * (half / direction.magnitude())
+    };
+    let mut polygons: Vec<Vec<Coord>> = edges
+        .iter()
+        .map(|edge| {
+            let (p, q) = *edge;
+            let normal = normal(edge);
+            vec![p + normal, q + normal, q - normal, p - normal]
+        })
+        .collect();
+
+    // Joins where the direction changes visibly
+    let join = |incoming: &(Coord, Coord), outgoing: &(Coord, Coord)| {
+        let (u, v) = (incoming.1 - incoming.0, outgoing.1 - outgoing.0);
+        let turn = (u.x * v.y - u.y * v.x).atan2(u.x * v.x + u.y * v.y);
+        if half * turn.abs() <= 0.25 {
+            return None;
+        }
+        let point = incoming.1;
+        // The outer side of a left turn is on the right
+        let side = match turn > 0.0 {
+            true => -1.0,
+            false => 1.0,
+        };
+        let (before, after) = (normal(incoming) * side, normal(outgoing) * side);
+        let miter_ratio = 1.0 / (turn / 2.0).cos();
+        Some(match style.join {
+            LineJoin::Round => circle_polygon(point, half, 1.0),
+            LineJoin::Miter if miter_ratio <= style.miter_limit => {
+                let bisector = before + after;
+                let tip = point + bisector * (half * miter_ratio / bisector.magnitude());
+                vec![point, point + before, tip, point + after]
+            }
+            LineJoin::Miter | LineJoin::Bevel => vec![point, point + before, point + after],
+        })
+    };
+    let wrap = match (closed, edges.first(), edges.last()) {
+        (true, Some(first), Some(last)) => join(last, first),
+        _ => None,
+    };
+    let joins: Vec<Vec<Coord>> = edges
+        .windows(2)
+        .filter_map(|pair| join(&pair[0], &pair[1]))
+        .chain(wrap)
+        .collect();
+    polygons.extend(joins);
+
+    // Caps at the ends of open lines, in the direction the line leaves
+    if !closed {
+        let ends = match (edges.first(), edges.last()) {
+            (Some(first), Some(last)) => {
+                vec![(first.0, first.0 - first.1), (last.1, last.1 - last.0)]
+            }
+            // a line of zero length only gets round caps
+            _ => points
+                .first()
+                .map(|point| (*point, Coord { x: 0.0, y: 0.0 }))
+                .into_iter()
+                .collect(),
+        };
+        for (point, direction) in ends {
+            let length = direction.magnitude();
+            match (style.cap, length > 0.0) {
+                (LineCap::Round, _) => polygons.push(circle_polygon(point, half, 1.0)),
+                (LineCap::Square, true) => {
+                    let along = direction * (half / length);
+                    let normal = Coord {
+                        x: -along.y,
+                        y: along.x,
+                    };
+                    polygons.push(vec![
+                        point + normal,
+                        point + along + normal,
+                        point + along - normal,
+                        point - normal,
+                    ]);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    // Use one orientation, so that overlapping pieces never cancel out
+    for polygon in polygons.iter_mut() {
+        if signed_area(polygon) < 0.0 {
+            polygon.reverse();
+        }
+    }
+    polygons
+}
+
+/// A raster image the art forms can be drawn on.
+/// Colors are stored premultiplied by alpha.
+#[derive(Clone)]
+pub struct RasterCanvas {
+    width: usize,
+    height: usize,
+    /// Maps y-up coordinates to pixels.
+    to_pixels: AffineTransform,
+    /// Maps pixels back to y-up coordinates.
+    from_pixels: AffineTransform,
+    pixels: Vec<[f64; 4]>,
+}
+
+impl RasterCanvas {
+    /// Create a transparent image of `width` x `height` pixels
+    /// showing the region `view_box` (y pointing up).
+    pub fn new<T: CoordFloat>(width: usize, height: usize, view_box: Rect<T>) -> Self {
+        or_panic(Self::try_new(width, height, view_box))
+    }
+
+    /// Create a transparent image, failing if it has no pixels or if `view_box`
+    /// isn't finite with a positive width and height.
+    pub fn try_new<T: CoordFloat>(width: usize, height: usize, view_box: Rect<T>) -> Result<Self> {
+        if width == 0 || height == 0 {
+            return Err(Error::EmptyImage);
+        }
+        let (min, max) = (view_box.min(), view_box.max());
+        let finite = [min.x, min.y, max.x, max.y]
+            .iter()
+            .all(|value| value.is_finite());
+        if !finite || view_box.width() <= T::zero() || view_box.height() <= T::zero() {
+            return Err(Error::InvalidViewBox);
+        }
+        let (min_x, max_y) = (min.x.to_f64().unwrap(), max.y.to_f64().unwrap());
+        let scale_x = width as f64 / view_box.width().to_f64().unwrap();
+        let scale_y = height as f64 / view_box.height().to_f64().unwrap();
+        let to_pixels = AffineTransform::new(
+            scale_x,
+            0.0,
+            -min_x * scale_x,
+            0.0,
+            -scale_y,
+            max_y * scale_y,
+        );
+        let from_pixels =
+            AffineTransform::new(1.0 / scale_x, 0.0, min_x, 0.0, -1.0 / scale_y, max_y);
+        Ok(RasterCanvas {
+            width,
+            height,
+            to_pixels,
+            from_pixels,
+            pixels: vec![[0.0; 4]; width * height],
+        })
+    }
+
+    /// Put a background of `color` behind everything drawn so far.
+    pub fn with_background(mut self, color: Color) -> Self {
+        let [r, g, b, a] = channels(&color);
+        for pixel in self.pixels.iter_mut() {
+            let behind = 1.0 - pixel[3];
+            pixel[0] += r * a * behind;
+            pixel[1] += g * a * behind;
+            pixel[2] += b * a * behind;
+            pixel[3] += a * behind;
+        }
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Straight (not premultiplied) RGBA components of the pixel in column `x` and row `y`.
+    pub fn pixel(&self, x: usize, y: usize) -> [u8; 4] {
+        let [r, g, b, a] = self.pixels[y * self.width + x];
+        let straight = |c: f64| match a > 0.0 {
+            true => (c / a * 255.0).round().clamp(0.0, 255.0) as u8,
+            false => 0,
+        };
+        [
+            straight(r),
+            straight(g),
+            straight(b),
+            (a * 255.0).round() as u8,
+        ]
+    }
+
+    /// RGBA bytes of all pixels, row by row from the top.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .flat_map(|(x, y)| self.pixel(x, y))
+            .collect()
+    }
+
+    /// The image encoded as PNG.
+    ///
+    /// The pixels are stored without compression, so the encoded image takes a little
+    /// over `width * height * 4` bytes.
+    pub fn to_png(&self) -> Vec<u8> {
+        // `to_rgba` gives exactly one pixel per position
+        encode_rgba(self.width, self.height, &self.to_rgba()).expect("one pixel per position")
+    }
+
+    /// Write the image as an uncompressed PNG file, see [`RasterCanvas::to_png`].
+    pub fn save_png<P: AsRef<std::path::Path>>(&self, path: P) -> io::Result<()> {
+        std::fs::write(path, self.to_png())
+    }
+
+    /// Length of one unit in pixels.
+    fn pixels_per_unit(&self) -> f64 {
+        let origin = self.to_pixels.apply(Coord { x: 0.0, y: 0.0 });
+        let x = self.to_pixels.apply(Coord { x: 1.0, y: 0.0 }) - origin;
+        let y = self.to_pixels.apply(Coord { x: 0.0, y: 1.0 }) - origin;
+        (x.x * y.y - x.y * y.x).abs().sqrt()
+    }
+
+    /// Colour of `paint` at every pixel, with its alpha scaled by `opacity`.
+    /// Returns `None` for paints that don't paint anything.
+    fn paint_source<T: CoordFloat>(&self, paint: &Paint<T>, opacity: T) -> Option<PaintSource> {
+        let number = |value: T| value.to_f64().unwrap();
+        let coord = |point: Point<T>| Coord {
+            x: number(point.x()),
+            y: number(point.y()),
+        };
+        let opacity = number(opacity).clamp(0.0, 1.0);
+        let with_opacity = move |[r, g, b, a]: [f64; 4]| [r, g, b, a * opacity];
+        // Stop offsets never decrease, see the svg specification
+        let stops = |stops: &[GradientStop<T>]| {
+            let stops: Vec<(f64, [f64; 4])> = stops
+                .iter()
+                .scan(0.0f64, |previous, stop| {
+                    *previous = previous.max(number(stop.offset).clamp(0.0, 1.0));
+                    Some((*previous, channels(&stop.color)))
+                })
+                .collect();
+            Some(stops).filter(|stops| !stops.is_empty())
+        };
+        let from_pixels = self.from_pixels;
+        match paint {
+            Paint::None => None,
+            Paint::Color(color) => {
+                let color = with_opacity(channels(color));
+                Some(Box::new(move |_| color))
+            }
+            Paint::LinearGradient(gradient) => {
+                let stops = stops(&gradient.stops)?;
+                let (start, end) = (coord(gradient.start), coord(gradient.end));
+                let direction = end - start;
+                let length = direction.x * direction.x + direction.y * direction.y;
+                Some(Box::new(move |pixel| {
+                    let point = from_pixels.apply(pixel) - start;
+                    let t = match length > 0.0 {
+                        true => (point.x * direction.x + point.y * direction.y) / length,
+                        false => 1.0,
+                    };
+                    with_opacity(gradient_color(&stops, t))
+                }))
+            }
+            Paint::RadialGradient(gradient) => {
+                let stops = stops(&gradient.stops)?;
+                let center = coord(gradient.center);
+                let focus = gradient.focus.map_or(center, coord);
+                let radius = number(gradient.radius);
+                Some(Box::new(move |pixel| {
+                    let point = from_pixels.apply(pixel);
+                    let t = radial_parameter(point, center, focus, radius);
+                    with_opacity(gradient_color(&stops, t))
+                }))
+            }
+            Paint::Pattern(pattern) => {
+                let (width, height) = (number(pattern.tile.width()), number(pattern.tile.height()));
+                if width <= 0.0 || height <= 0.0 {
+                    return None;
+                }
+                // The tile is rendered once, at the resolution of this canvas
+                let scale = self.pixels_per_unit();
+                let columns = ((width * scale).ceil() as usize).max(1);
+                let rows = ((height * scale).ceil() as usize).max(1);
+                let tile = pattern
+                    .content
+                    .render(RasterCanvas::try_new(columns, rows, pattern.tile).ok()?);
+                let (min_x, max_y) = (number(pattern.tile.min().x), number(pattern.tile.max().y));
+                Some(Box::new(move |pixel| {
+                    let point = from_pixels.apply(pixel);
+                    let column = (point.x - min_x).rem_euclid(width) / width * columns as f64;
+                    let row = (max_y - point.y).rem_euclid(height) / height * rows as f64;
+                    let index =
+                        (row as usize).min(rows - 1) * columns + (column as usize).min(columns - 1);
+                    let [r, g, b, a] = tile.pixels[index];
+                    match a > 0.0 {
+                        true => with_opacity([r / a, g / a, b / a, a]),
+                        false => [0.0; 4],
+                    }
+                }))
+            }
+        }
+    }
+
+    /// Composite `paint` over the pixels covered by the polygons (in pixel coordinates).
+    fn fill_polygons(&mut self, polygons: &[Vec<Coord>], paint: &PaintSource, even_odd: bool) {
+        let edges: Vec<(Coord, Coord)> = polygons
+            .iter()
+            .filter(|polygon| polygon.len() > 2)
+            .flat_map(|polygon| {
+                polygon
+                    .iter()
+                    .zip(polygon.iter().cycle().skip(1))
+                    .map(|(p, q)| (*p, *q))
+            })
+            .filter(|(p, q)| p.y != q.y)
+            .collect();
+        if edges.is_empty() {
+            return;
+        }
+        // Edges crossing every pixel row
+        let mut rows: Vec<Vec<usize>> = vec![vec![]; self.height];
+        for (index, (p, q)) in edges.iter().enumerate() {
+            let first = p.y.min(q.y).floor().max(0.0) as usize;
+            let last = (p.y.max(q.y).ceil().max(0.0) as usize).min(self.height);
+            for row in rows.iter_mut().take(last).skip(first) {
+                row.push(index);
+            }
+        }
+        let mut coverage = vec![0.0; self.width];
+        for (row, row_edges) in rows.iter().enumerate() {
+            if row_edges.is_empty() {
+                continue;
+            }
+            coverage.iter_mut().for_each(|c| *c = 0.0);
+            for sub in 0..SUB_SCANLINES {
+                let y = row as f64 + (sub as f64 + 0.5) / SUB_SCANLINES as f64;
+                let mut crossings: Vec<(f64, i32)> = row_edges
+                    .iter()
+                    .map(|index| edges[*index])
+                    .filter(|(p, q)| p.y.min(q.y) <= y && y < p.y.max(q.y))
+                    .map(|(p, q)| {
+                        let x = p.x + (y - p.y) * (q.x - p.x) / (q.y - p.y);
+                        (x, if q.y > p.y { 1 } else { -1 })
+                    })
+                    .collect();
+                crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+                let mut winding = 0;
+                for pair in crossings.windows(2) {
+                    winding += pair[0].1;
+                    let inside = match even_odd {
+                        true => winding % 2 != 0,
+                        false => winding != 0,
+                    };
+                    if !inside {
+                        continue;
+                    }
+                    let (start, end) = (pair[0].0.max(0.0), pair[1].0.min(self.width as f64));
+                    if start >= end {
+                        continue;
+                    }
+                    let (first, last) = (start.floor() as usize, end.ceil() as usize);
+                    let columns = coverage.iter_mut().enumerate().take(last).skip(first);
+                    for (column, covered) in columns {
+                        let overlap = end.min(column as f64 + 1.0) - start.max(column as f64);
+                        *covered += overlap.max(0.0) / SUB_SCANLINES as f64;
+                    }
+                }
+            }
+            for (column, covered) in coverage.iter().enumerate() {
+                if *covered <= 0.0 {
+                    continue;
+                }
+                let color = paint(Coord {
+                    x: column as f64 + 0.5,
+                    y: row as f64 + 0.5,
+                });
+                let alpha = covered.min(1.0) * color[3];
+                let pixel = &mut self.pixels[row * self.width + column];
+                for channel in 0..3 {
+                    pixel[channel] = color[channel] * alpha + pixel[channel] * (1.0 - alpha);
+                }
+                pixel[3] = alpha + pixel[3] * (1.0 - alpha);
+            }
+        }
+    }
+
+    /// Fill and stroke sub-paths given in pixel coordinates.
+    fn paint<T: CoordFloat>(&mut self, lines: Vec<(Vec<Coord>, bool)>, config: &Config<T>) {
+        let number = |value: T| value.to_f64().unwrap();
+        if let Some(fill) = self.paint_source(&config.fill(), config.fill_opacity) {
+            let polygons: Vec<Vec<Coord>> =
+                lines.iter().map(|(points, _)| points.clone()).collect();
+            let even_odd = config.fill_rule == FillRule::EvenOdd;
+            self.fill_polygons(&polygons, &fill, even_odd);
+        }
+        let scale = self.pixels_per_unit();
+        let style = StrokeStyle {
+            width: number(config.stroke_width) * scale,
+            cap: config.line_cap,
+            join: config.line_join,
+            miter_limit: number(config.miter_limit),
+        };
+        let stroke = self.paint_source(&config.stroke(), config.stroke_opacity);
+        if let (Some(stroke), true) = (stroke, style.width > 0.0) {
+            let pattern: Vec<f64> = config
+                .dash_array
+                .iter()
+                .map(|length| number(*length) * scale)
+                .collect();
+            let offset = number(config.dash_offset) * scale;
+            let polygons: Vec<Vec<Coord>> = lines
+                .iter()
+                .flat_map(
+                    |(points, closed)| match dashes(points, *closed, &pattern, offset) {
+                        Some(dashes) => dashes.into_iter().map(|dash| (dash, false)).collect(),
+                        None => vec![(points.clone(), *closed)],
+                    },
+                )
+                .flat_map(|(points, closed)| stroke_polygons(&points, closed, &style))
+                .collect();
+            self.fill_polygons(&polygons, &stroke, false);
+        }
+    }
+}
+
+/*====== Backend Implementation for raster images ============ */
+impl<T: CoordFloat> Backend<T> for RasterCanvas {
+    fn draw_circle(mut self, radius: T, center: Point<T>, config: Config<T>) -> Self {
+        let center = self.to_pixels.apply(Coord {
+            x: center.x().to_f64().unwrap(),
+            y: center.y().to_f64().unwrap(),
+        });
+        let radius = radius.to_f64().unwrap() * self.pixels_per_unit();
+        let circle = circle_polygon(center, radius, 1.0);
+        self.paint(vec![(circle, true)], &config);
+        self
+    }
+
+    fn draw_path(mut self, path: Path<T>, config: Config<T>) -> Self {
+        let lines = path_sub_paths(&path)
+            .into_iter()
+            .map(|sub_path| {
+                let segments = sub_path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.transformed(&self.to_pixels))
+                    .collect();
+                let sub_path = SubPath {
+                    segments,
+                    closed: sub_path.closed,
+                };
+                (flatten(&sub_path), sub_path.closed)
+            })
+            .collect();
+        self.paint(lines, &config);
+        self
+    }
+}
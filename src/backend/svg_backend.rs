@@ -0,0 +1,257 @@
+use geo::{Coord, Point, Rect};
+use geo_types::CoordFloat;
+use svg::node::element::path::Data;
+use svg::node::element::path::Parameters;
+use svg::node::element::Circle;
+use svg::node::element::Element;
+use svg::node::element::Path as SvgPath;
+use svg::node::Value;
+use svg::Node;
+
+type Canvas = crate::canvas::SvgCanvas;
+
+use super::backend_trait::Backend;
+use crate::art_forms::base_shapes::{Config, SetConfig};
+use crate::canvas::bounds::add_svg_content_bounds;
+use crate::canvas::canvas_impl::{add_definition, from_svg_rect, merge_definitions, svg_point};
+use crate::canvas::YAxis;
+use crate::paint::{Paint, Pattern};
+use crate::path::{Path, PathCommand};
+use crate::scene::{Geometry, Scene, Shape};
+
+/// Svg path data of a path given in the coordinates of a canvas with `y_axis`.
+/// Flipping the y-axis reverses the orientation of arcs, so their sweep flag and rotation flip along.
+pub(crate) fn svg_data<T: CoordFloat>(path: &Path<T>, y_axis: YAxis) -> Data
+where
+    Parameters: From<T>,
+{
+    let flipped = y_axis == YAxis::Up;
+    let convert = |point: &Point<T>| svg_point(*point, y_axis);
+    path.commands()
+        .iter()
+        .map(|command| match command {
+            PathCommand::MoveTo(point) => PathCommand::MoveTo(convert(point)),
+            PathCommand::LineTo(point) => PathCommand::LineTo(convert(point)),
+            PathCommand::QuadraticTo(control, end) => {
+                PathCommand::QuadraticTo(convert(control), convert(end))
+            }
+            PathCommand::CubicTo(control_1, control_2, end) => {
+                PathCommand::CubicTo(convert(control_1), convert(control_2), convert(end))
+            }
+            PathCommand::ArcTo(rx, ry, x_rotation, large_arc, sweep, end) => match flipped {
+                true => {
+                    PathCommand::ArcTo(*rx, *ry, -*x_rotation, *large_arc, !*sweep, convert(end))
+                }
+                false => command.clone(),
+            },
+            PathCommand::Close => PathCommand::Close,
+        })
+        .fold(Data::new(), |data, command| match &command {
+            PathCommand::MoveTo(point) => data.move_to(point.x_y()),
+            PathCommand::LineTo(point) => data.line_to(point.x_y()),
+            PathCommand::QuadraticTo(control, end) => {
+                data.quadratic_curve_to((control.x(), control.y(), end.x(), end.y()))
+            }
+            PathCommand::CubicTo(control_1, control_2, end) => data.cubic_curve_to((
+                control_1.x(),
+                control_1.y(),
+                control_2.x(),
+                control_2.y(),
+                end.x(),
+                end.y(),
+            )),
+            PathCommand::ArcTo(rx, ry, x_rotation, large_arc, sweep, end) => data
+                .elliptical_arc_to((
+                    *rx,
+                    *ry,
+                    *x_rotation,
+                    *large_arc as u8,
+                    *sweep as u8,
+                    end.x(),
+                    end.y(),
+                )),
+            PathCommand::Close => data.close(),
+        })
+}
+
+/// Pattern element with its tile content rendered in svg coordinates,
+/// along with the canvas holding the definitions the content uses.
+/// The view box of the pattern is the tile, so the content keeps its coordinates.
+fn pattern_element<T: CoordFloat>(pattern: &Pattern<T>, y_axis: YAxis) -> (Element, Canvas)
+where
+    Value: From<T>,
+    Parameters: From<T>,
+{
+    let tile = from_svg_rect(pattern.tile, y_axis);
+    let mut element = Element::new("pattern");
+    element.assign("patternUnits", "userSpaceOnUse");
+    element.assign("x", tile.min().x);
+    element.assign("y", tile.min().y);
+    element.assign("width", tile.width());
+    element.assign("height", tile.height());
+    element.assign(
+        "viewBox",
+        (tile.min().x, tile.min().y, tile.width(), tile.height()),
+    );
+    let mut content = pattern.content.render(Canvas::with_y_axis(y_axis));
+    for child in content.get_children_mut().drain(..) {
+        element.append(child);
+    }
+    (element, content)
+}
+
+/// Add the gradient or pattern element of `paint` in svg coordinates to the definitions
+/// of the canvas, nothing for colours.
+fn add_paint_definition<T: CoordFloat>(canvas: Canvas, paint: &Paint<T>) -> Canvas
+where
+    Value: From<T>,
+    Parameters: From<T>,
+{
+    let id = match paint.definition_id() {
+        Some(id) if !canvas.has_definition(&id) => id,
+        _ => return canvas,
+    };
+    let y_axis = canvas.y_axis;
+    let (mut gradient, stops) = match paint {
+        Paint::None | Paint::Color(_) => return canvas,
+        Paint::Pattern(pattern) => {
+            let (mut element, content) = pattern_element(pattern, y_axis);
+            element.assign("id", id.clone());
+            // the gradients and patterns of the tile are defined next to the pattern
+            return add_definition(merge_definitions(canvas, &content), &id, element);
+        }
+        Paint::LinearGradient(gradient) => {
+            let (start, end) = (
+                svg_point(gradient.start, y_axis),
+                svg_point(gradient.end, y_axis),
+            );
+            let mut element = Element::new("linearGradient");
+            element.assign("x1", start.x());
+            element.assign("y1", start.y());
+            element.assign("x2", end.x());
+            element.assign("y2", end.y());
+            (element, &gradient.stops)
+        }
+        Paint::RadialGradient(gradient) => {
+            let center = svg_point(gradient.center, y_axis);
+            let mut element = Element::new("radialGradient");
+            element.assign("cx", center.x());
+            element.assign("cy", center.y());
+            element.assign("r", gradient.radius);
+            if let Some(focus) = gradient.focus {
+                let focus = svg_point(focus, y_axis);
+                element.assign("fx", focus.x());
+                element.assign("fy", focus.y());
+            }
+            (element, &gradient.stops)
+        }
+    };
+    gradient.assign("id", id.clone());
+    gradient.assign("gradientUnits", "userSpaceOnUse");
+    for stop in stops {
+        let mut element = Element::new("stop");
+        element.assign("offset", stop.offset);
+        element.assign("stop-color", stop.color.to_string());
+        gradient.append(element);
+    }
+    add_definition(canvas, &id, gradient)
+}
+
+/// Add the definitions of the gradients and patterns used by `config`, each is defined once.
+fn add_paint_definitions<T: CoordFloat>(canvas: Canvas, config: &Config<T>) -> Canvas
+where
+    Value: From<T>,
+    Parameters: From<T>,
+{
+    [config.fill(), config.stroke()]
+        .iter()
+        .fold(canvas, add_paint_definition)
+}
+
+fn circle<T: CoordFloat>(canvas: &Canvas, radius: T, center: Point<T>, config: Config<T>) -> Circle
+where
+    Value: From<T>,
+    Parameters: From<T>,
+{
+    let center = svg_point(center, canvas.y_axis);
+    let mut circle = Circle::new();
+    circle.assign("cx", center.x());
+    circle.assign("cy", center.y());
+    circle.assign("r", radius);
+    circle.set_config(config)
+}
+
+fn path<T: CoordFloat>(canvas: &Canvas, path: &Path<T>, config: Config<T>) -> SvgPath
+where
+    Value: From<T>,
+    Parameters: From<T>,
+{
+    let data = svg_data(path, canvas.y_axis);
+    SvgPath::new().set_config(config).set("d", data)
+}
+
+/// Bounds of a shape drawn on a canvas with `y_axis`, in svg coordinates.
+fn svg_bounds<T: CoordFloat>(shape: &Shape<T>, y_axis: YAxis) -> Option<Rect<f64>> {
+    let rect = from_svg_rect(shape.bounds()?, y_axis);
+    let coord = |coord: Coord<T>| {
+        Some(Coord {
+            x: coord.x.to_f64()?,
+            y: coord.y.to_f64()?,
+        })
+    };
+    Some(Rect::new(coord(rect.min())?, coord(rect.max())?))
+}
+
+/// Add the svg element of a shape with its id to the canvas,
+/// along with the definitions of its paints and its bounds.
+fn render_shape<T: CoordFloat>(canvas: Canvas, shape: &Shape<T>) -> Canvas
+where
+    Value: From<T>,
+    Parameters: From<T>,
+{
+    let config = shape.config.clone();
+    let id = shape.id.clone();
+    let bounds = svg_bounds(shape, canvas.y_axis);
+    let canvas = add_svg_content_bounds(add_paint_definitions(canvas, &config), bounds);
+    match &shape.geometry {
+        Geometry::Circle(radius, center) => {
+            let circle = circle(&canvas, *radius, *center, config);
+            canvas.add(match id {
+                Some(id) => circle.set("id", id),
+                None => circle,
+            })
+        }
+        Geometry::Path(path) => {
+            let path = self::path(&canvas, path, config);
+            canvas.add(match id {
+                Some(id) => path.set("id", id),
+                None => path,
+            })
+        }
+    }
+}
+
+/*====== Backend Implementation for svg documents ============ */
+/// Circles and paths are recorded in a [`Scene`] which is replayed on the canvas,
+/// so drawing on a canvas and rendering a scene of the same drawing give the same document.
+impl<T: CoordFloat> Backend<T> for Canvas
+where
+    Value: From<T>,
+    Parameters: From<T>,
+{
+    fn draw_circle(self, radius: T, center: Point<T>, config: Config<T>) -> Self {
+        Scene::new()
+            .draw_circle(radius, center, config)
+            .render(self)
+    }
+
+    fn draw_path(self, path: Path<T>, config: Config<T>) -> Self {
+        Scene::new().draw_path(path, config).render(self)
+    }
+
+    /// The id of the shape becomes the id of the element.
+    /// The bounds of the shape are added to the recorded bounds of the canvas.
+    fn draw_shape(self, shape: &Shape<T>) -> Self {
+        render_shape(self, shape)
+    }
+}
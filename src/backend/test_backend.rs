@@ -0,0 +1,365 @@
+use super::png::encode_rgba;
+use super::{Backend, RasterCanvas};
+use crate::art_forms::base_shapes::{BaseShapes, Config};
+use crate::art_forms::leaves::{LeafStyle, Leaves};
+use crate::art_forms::yantra::Yantra;
+use crate::color::Color;
+use crate::error::Error;
+use crate::paint::Paint;
+use crate::path::Path;
+
+use crate::canvas::SvgCanvas;
+use geo::{point, Rect};
+
+type Canvas = SvgCanvas;
+type T = f64;
+
+/// Draw the same composition on any backend.
+fn draw<B: Backend<T>>(backend: B) -> B {
+    let center = point!(x: 0.0, y: 0.0);
+    let radius = 100.0;
+    let outer = Config::<T>::try_new(1.0, "none", "#7c96f7").unwrap();
+    let inner = Config::<T>::try_new(1.0, "none", "#E8FB62").unwrap();
+    let configs = [0, 1, 2, 3, 4, 5, 6, 7, 8].map(|i| match i % 2 {
+        0 => outer.clone(),
+        _ => inner.clone(),
+    });
+    let leaf_config = Config::<T>::try_new(2.0, "#900007", "pink").unwrap();
+    let reniform_style = LeafStyle::<T>::new_reniform(25.0, 1.1, 12.0, 13.0);
+    backend
+        .add_circle(radius, center, inner.clone())
+        .add_circular_leaves(
+            radius,
+            center,
+            -360.0 / 32.0,
+            16,
+            1.0,
+            reniform_style,
+            leaf_config,
+        )
+        .add_sri(radius, center, configs)
+        .add_circle(
+            3.0,
+            center,
+            Config::try_new(0.0, "none", "rgb(243, 54, 37)").unwrap(),
+        )
+}
+
+#[test]
+fn test_raster_backend() {
+    let view_box = Rect::new(point!(x: -150.0, y: -150.0), point!(x: 150.0, y: 150.0));
+    let raster = draw(RasterCanvas::new(300, 300, view_box));
+    // the bindu is in the center, the corners are transparent
+    assert_eq!(raster.pixel(150, 150), [243, 54, 37, 255]);
+    assert_eq!(raster.pixel(0, 0), [0, 0, 0, 0]);
+    // a leaf tip on the positive x-axis, drawn in pink
+    assert_eq!(raster.pixel(270, 150), [255, 192, 203, 255]);
+
+    let png = raster.with_background(Color::WHITE).to_png();
+    assert_eq!(&png[..8], &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]);
+    assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+}
+
+#[test]
+fn test_raster_canvas_rejects_empty_images_and_view_boxes() {
+    let view_box = Rect::new(point!(x: 0.0, y: 0.0), point!(x: 10.0, y: 10.0));
+    assert_eq!(
+        RasterCanvas::try_new(0, 10, view_box).err(),
+        Some(Error::EmptyImage)
+    );
+    assert_eq!(
+        RasterCanvas::try_new(10, 0, view_box).err(),
+        Some(Error::EmptyImage)
+    );
+    let flat = Rect::new(point!(x: 0.0, y: 0.0), point!(x: 10.0, y: 0.0));
+    assert_eq!(
+        RasterCanvas::try_new(10, 10, flat).err(),
+        Some(Error::InvalidViewBox)
+    );
+    let infinite = Rect::new(point!(x: 0.0, y: 0.0), point!(x: T::INFINITY, y: 10.0));
+    assert_eq!(
+        RasterCanvas::try_new(10, 10, infinite).err(),
+        Some(Error::InvalidViewBox)
+    );
+    let nan = Rect::new(point!(x: T::NAN, y: 0.0), point!(x: 10.0, y: 10.0));
+    assert_eq!(
+        RasterCanvas::try_new(10, 10, nan).err(),
+        Some(Error::InvalidViewBox)
+    );
+    assert!(RasterCanvas::try_new(10, 10, view_box).is_ok());
+}
+
+#[test]
+fn test_png_needs_one_pixel_per_position() {
+    assert_eq!(
+        encode_rgba(2, 2, &[0; 12]),
+        Err(Error::PixelCount {
+            expected: 4,
+            found: 3
+        })
+    );
+    // stored without compression: signature, header, the rows with their filter byte
+    // in one deflate block, and the end
+    let png = encode_rgba(2, 2, &[0; 16]).unwrap();
+    assert_eq!(png.len(), 8 + (12 + 13) + (12 + 2 + 5 + 2 * 9 + 4) + 12);
+}
+
+#[test]
+fn test_raster_y_axis_points_up() {
+    let view_box = Rect::new(point!(x: 0.0, y: 0.0), point!(x: 10.0, y: 10.0));
+    let config = Config::<T>::try_new(0.0, "none", "black").unwrap();
+    let square = Path::from_points(
+        vec![
+            point!(x: 0.0, y: 5.0),
+            point!(x: 5.0, y: 5.0),
+            point!(x: 5.0, y: 10.0),
+            point!(x: 0.0, y: 10.0),
+        ],
+        true,
+    );
+    let raster = RasterCanvas::new(10, 10, view_box).draw_path(square, config);
+    assert_eq!(raster.pixel(2, 2), [0, 0, 0, 255]);
+    assert_eq!(raster.pixel(2, 7), [0, 0, 0, 0]);
+    assert_eq!(raster.pixel(7, 2), [0, 0, 0, 0]);
+}
+
+#[test]
+fn test_svg_backend() {
+    let view_size = 150.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let canvas = draw(canvas);
+    let content = canvas.to_string();
+    assert_eq!(content.matches("<path").count(), 16 + 9);
+    svg::save("./unit_tests/backend/svg_backend.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_svg_backend_renders_like_a_scene() {
+    use crate::canvas::{Canvas as _, YAxis};
+    use crate::scene::Scene;
+    let view_box = Rect::new(point!(x: -150.0, y: -150.0), point!(x: 150.0, y: 150.0));
+    for y_axis in [YAxis::Up, YAxis::Down] {
+        let drawn = draw(Canvas::with_view_box(view_box, y_axis));
+        let scene = draw(Scene::new());
+        let rendered = scene.render(Canvas::with_view_box(view_box, y_axis));
+        assert_eq!(drawn.to_string(), rendered.to_string());
+        let bounds = |canvas: &Canvas| -> Option<Rect<T>> { canvas.content_bounds() };
+        assert_eq!(bounds(&drawn), bounds(&rendered));
+    }
+}
+
+#[test]
+fn test_raster_fill_rule_and_stroke_style() {
+    use crate::art_forms::base_shapes::{FillRule, LineCap, LineJoin};
+    let view_box = Rect::new(point!(x: -50.0, y: -50.0), point!(x: 50.0, y: 50.0));
+    let black = Config::<T>::try_new(0.0, "none", "black").unwrap();
+    let star = |config: Config<T>| {
+        RasterCanvas::new(100, 100, view_box).add_star_polygon(
+            40.0,
+            point!(x: 0.0, y: 0.0),
+            90.0,
+            5,
+            2,
+            config,
+        )
+    };
+    // the pentagon in the middle of a pentagram is covered twice
+    assert_eq!(star(black.clone()).pixel(50, 50), [0, 0, 0, 255]);
+    let even_odd = star(black.clone().with_fill_rule(FillRule::EvenOdd));
+    assert_eq!(even_odd.pixel(50, 50), [0, 0, 0, 0]);
+    let translucent = star(black.with_fill_opacity(0.5));
+    assert_eq!(translucent.pixel(50, 50)[3], 128);
+
+    // a dashed line with butt caps has gaps, square caps extend it beyond its ends
+    let line = Path::from_points(
+        vec![point!(x: -40.0, y: 0.0), point!(x: 40.0, y: 0.0)],
+        false,
+    );
+    let stroke = Config::<T>::try_new(4.0, "black", "none").unwrap();
+    let dashed = RasterCanvas::new(100, 100, view_box).draw_path(
+        line.clone(),
+        stroke.clone().with_dashes(vec![10.0, 10.0], 0.0),
+    );
+    assert_eq!(dashed.pixel(15, 50), [0, 0, 0, 255]);
+    assert_eq!(dashed.pixel(25, 50), [0, 0, 0, 0]);
+    let square = RasterCanvas::new(100, 100, view_box)
+        .draw_path(line, stroke.clone().with_line_cap(LineCap::Square));
+    assert_eq!(square.pixel(9, 50), [0, 0, 0, 255]);
+    assert_eq!(square.pixel(7, 50), [0, 0, 0, 0]);
+
+    // the outer corner of a right angle is filled by a miter join, partly by a round join
+    let corner = Path::from_points(
+        vec![
+            point!(x: -20.0, y: 0.0),
+            point!(x: 0.0, y: 0.0),
+            point!(x: 0.0, y: -20.0),
+        ],
+        false,
+    );
+    let join = |line_join| {
+        RasterCanvas::new(100, 100, view_box)
+            .draw_path(
+                corner.clone(),
+                stroke.clone().with_line_join(line_join, 4.0),
+            )
+            .pixel(51, 48)
+    };
+    assert_eq!(join(LineJoin::Miter), [0, 0, 0, 255]);
+    assert_eq!(join(LineJoin::Bevel)[3], 0);
+    assert!((1..255).contains(&join(LineJoin::Round)[3]));
+}
+
+#[test]
+fn test_svg_gradients_are_defined_once() {
+    use crate::paint::{LinearGradient, RadialGradient};
+    let view_size = 150.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let center = point!(x: 0.0, y: 0.0);
+    let petal = RadialGradient::new(center, 130.0)
+        .stop(0.7, Color::rgb(0xfd, 0x9d, 0x97))
+        .stop(1.0, Color::rgb(0x90, 0x00, 0x07));
+    let sky = LinearGradient::new(point!(x: 0.0, y: -100.0), point!(x: 0.0, y: 100.0))
+        .stop(0.0, Color::rgb(0x7c, 0x96, 0xf7))
+        .stop(1.0, Color::WHITE);
+    let reniform_style = LeafStyle::<T>::new_reniform(25.0, 1.1, 12.0, 13.0);
+    let canvas = canvas
+        .add_circle(100.0, center, Config::try_new(1.0, "none", sky).unwrap())
+        .add_circular_leaves(
+            100.0,
+            center,
+            0.0,
+            16,
+            1.0,
+            reniform_style,
+            Config::try_new(1.0, "#900007", petal).unwrap(),
+        );
+    let content = canvas.to_string();
+    assert_eq!(content.matches("<radialGradient").count(), 1);
+    assert_eq!(content.matches("<linearGradient").count(), 1);
+    assert_eq!(content.matches(r#"fill="url(#gradient-"#).count(), 16 + 1);
+    // gradients are converted to svg coordinates like the shapes
+    assert!(content.contains(r#"y1="100""#));
+    assert!(content.contains(r#"gradientUnits="userSpaceOnUse""#));
+    svg::save("./unit_tests/backend/svg_gradients.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_raster_gradients() {
+    use crate::paint::{LinearGradient, RadialGradient};
+    let view_box = Rect::new(point!(x: 0.0, y: 0.0), point!(x: 100.0, y: 100.0));
+    let square = Path::from_points(
+        vec![
+            point!(x: 0.0, y: 0.0),
+            point!(x: 100.0, y: 0.0),
+            point!(x: 100.0, y: 100.0),
+            point!(x: 0.0, y: 100.0),
+        ],
+        true,
+    );
+    let linear = LinearGradient::new(point!(x: 20.0, y: 0.0), point!(x: 80.0, y: 0.0))
+        .stop(0.0, Color::BLACK)
+        .stop(1.0, Color::WHITE);
+    let raster = RasterCanvas::new(100, 100, view_box).draw_path(
+        square.clone(),
+        Config::try_new(0.0, "none", linear).unwrap(),
+    );
+    // padded beyond the ends, interpolated in between
+    assert_eq!(raster.pixel(5, 50), [0, 0, 0, 255]);
+    assert_eq!(raster.pixel(95, 50), [255, 255, 255, 255]);
+    assert_eq!(raster.pixel(49, 10)[0], 125);
+
+    let radial = RadialGradient::new(point!(x: 50.0, y: 50.0), 40.0)
+        .stop(0.0, Color::rgb(255, 0, 0))
+        .stop(1.0, Color::rgb(0, 0, 255));
+    let raster = RasterCanvas::new(100, 100, view_box)
+        .draw_path(square, Config::try_new(0.0, "none", radial).unwrap());
+    assert!(raster.pixel(49, 49)[0] > 240);
+    assert_eq!(raster.pixel(2, 2), [0, 0, 255, 255]);
+}
+
+#[test]
+fn test_svg_patterns_are_defined_once() {
+    use crate::paint::Pattern;
+    use crate::scene::Scene;
+    let view_size = 150.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let tile = Rect::new(point!(x: 0.0, y: 0.0), point!(x: 20.0, y: 20.0));
+    let hexagon = Scene::new()
+        .add_circle(
+            10.0,
+            point!(x: 10.0, y: 10.0),
+            Config::try_new(0.0, "none", "#fd9d97").unwrap(),
+        )
+        .add_regular_n_gon(
+            8.0,
+            point!(x: 10.0, y: 10.0),
+            0.0,
+            6,
+            Config::try_new(1.0, "#900007", "none").unwrap(),
+        );
+    let pattern = Pattern::new(tile, hexagon);
+    let center = point!(x: 0.0, y: 0.0);
+    let reniform_style = LeafStyle::<T>::new_reniform(25.0, 1.1, 12.0, 13.0);
+    let canvas = canvas
+        .add_circle(
+            60.0,
+            center,
+            Config::try_new(1.0, "black", pattern.clone()).unwrap(),
+        )
+        .add_circular_leaves(
+            100.0,
+            center,
+            0.0,
+            8,
+            1.0,
+            reniform_style,
+            Config::try_new(1.0, "#900007", pattern).unwrap(),
+        );
+    let content = canvas.to_string();
+    assert_eq!(content.matches("<pattern").count(), 1);
+    assert!(content.contains(r#"patternUnits="userSpaceOnUse""#));
+    assert!(content.contains(r#"viewBox="0 -20 20 20""#));
+    assert_eq!(content.matches(r#"fill="url(#pattern-"#).count(), 8 + 1);
+    svg::save("./unit_tests/backend/svg_patterns.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_raster_patterns_repeat() {
+    use crate::paint::Pattern;
+    use crate::scene::Scene;
+    let view_box = Rect::new(point!(x: 0.0, y: 0.0), point!(x: 100.0, y: 100.0));
+    let square = Path::from_points(
+        vec![
+            point!(x: 0.0, y: 0.0),
+            point!(x: 100.0, y: 0.0),
+            point!(x: 100.0, y: 100.0),
+            point!(x: 0.0, y: 100.0),
+        ],
+        true,
+    );
+    // a red dot in the lower left corner of every 20 x 20 tile
+    let tile = Rect::new(point!(x: 0.0, y: 0.0), point!(x: 20.0, y: 20.0));
+    let dot = Scene::new().add_circle(
+        4.0,
+        point!(x: 5.0, y: 5.0),
+        Config::try_new(0.0, "none", "red").unwrap(),
+    );
+    let raster = RasterCanvas::new(100, 100, view_box).draw_path(
+        square,
+        Config::from_paints(0.0, Paint::None, Pattern::new(tile, dot)),
+    );
+    assert_eq!(raster.pixel(5, 94), [255, 0, 0, 255]);
+    assert_eq!(raster.pixel(45, 54), [255, 0, 0, 255]);
+    assert_eq!(raster.pixel(85, 14), [255, 0, 0, 255]);
+    assert_eq!(raster.pixel(15, 84)[3], 0);
+    assert_eq!(raster.pixel(5, 84)[3], 0);
+}
@@ -0,0 +1,42 @@
+//! Bounding box of the content of a canvas.
+//! The bounds are accumulated while drawing, in svg coordinates, and follow the content
+//! through transforms, clips and splices, so they never need to be recomputed from the document.
+//! They are kept next to the document by [`SvgCanvas`](super::SvgCanvas), not written into it.
+//! Transformed content is bounded by the transformed bounding box of the content.
+use geo::{AffineTransform, Coord, Rect};
+
+use crate::path::segment::{coords_bounds, intersection, union};
+
+type Canvas = crate::canvas::SvgCanvas;
+
+/// Bounds of a rectangle mapped by an affine transform.
+pub(crate) fn transformed_rect(rect: Rect, transform: &AffineTransform) -> Rect {
+    let (min, max) = (rect.min(), rect.max());
+    let corners = [
+        min,
+        Coord { x: min.x, y: max.y },
+        max,
+        Coord { x: max.x, y: min.y },
+    ];
+    coords_bounds(corners.map(|corner| transform.apply(corner))).unwrap()
+}
+
+/// Union of two optional rectangles.
+pub(crate) fn merge(bounds: Option<Rect>, other: Option<Rect>) -> Option<Rect> {
+    match (bounds, other) {
+        (Some(rect), Some(other)) => Some(union(rect, other)),
+        (rect, None) | (None, rect) => rect,
+    }
+}
+
+/// Grow the recorded bounds of a canvas to contain `bounds`, given in svg coordinates.
+pub(crate) fn add_svg_content_bounds(mut canvas: Canvas, bounds: Option<Rect>) -> Canvas {
+    canvas.bounds = merge(canvas.bounds, bounds);
+    canvas
+}
+
+/// Restrict the recorded bounds of a canvas to `clip`, given in svg coordinates.
+pub(crate) fn clip_svg_content_bounds(mut canvas: Canvas, clip: Rect) -> Canvas {
+    canvas.bounds = canvas.bounds.and_then(|rect| intersection(rect, clip));
+    canvas
+}
@@ -1,8 +1,124 @@
 //! Canvas trait is described here
 //! This should include methods for splicing, rotating, cropping, etc for a canvas
+use geo::AffineTransform;
+use geo::Point;
+use geo::Polygon;
+use geo::Rect;
+use geo_types::CoordFloat;
 
+use super::metadata::Unit;
+use crate::error::{or_panic, Result};
 
+/// Shapes a canvas can be clipped to.
+#[derive(Clone)]
+pub enum ClipShape<T: CoordFloat> {
+    /// An axis aligned rectangle.
+    Rectangle(Rect<T>),
+    /// A circle given by its radius and center.
+    Circle(T, Point<T>),
+    /// An arbitrary polygon, holes are respected.
+    Polygon(Polygon<T>),
+}
 
-pub trait Canvas {
+/// Direction of the y-axis of a canvas.
+/// Coordinates of everything drawn on a canvas, its view box and its transforms
+/// are given in this convention, the conversion to svg coordinates is done by the canvas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum YAxis {
+    /// Math convention, y points up and positive angles turn counter-clockwise.
+    #[default]
+    Up,
+    /// Svg convention, y points down and positive angles turn clockwise.
+    Down,
+}
 
-}
\ No newline at end of file
+/// Whole-canvas operations.
+/// All coordinates and angles are in the same convention as the art forms,
+/// i.e., in the direction of the [`YAxis`] of the canvas and angles in degrees.
+/// `Self` is consumed everywhere in order to enable a builder pattern API.
+pub trait Canvas<T: CoordFloat>
+where
+    Self: Sized,
+{
+    /// An empty canvas showing `view_box`, with the y-axis pointing in the direction of `y_axis`.
+    /// Canvases created otherwise have their y-axis pointing up.
+    fn with_view_box(view_box: Rect<T>, y_axis: YAxis) -> Self;
+
+    /// Apply an affine transform to everything drawn on the canvas so far.
+    fn transform(self, transform: AffineTransform<T>) -> Self;
+
+    /// Clip everything drawn on the canvas so far to `shape`.
+    fn clip(self, shape: ClipShape<T>) -> Self;
+
+    /// Clip everything drawn by `draw` to `shape`, leaving the existing content untouched.
+    fn clip_with<F>(self, shape: ClipShape<T>, draw: F) -> Self
+    where
+        F: FnOnce(Self) -> Self;
+
+    /// Crop the canvas to `rect`: the content is clipped and the view box is set to `rect`.
+    fn crop(self, rect: Rect<T>) -> Self;
+
+    /// Bounding box of everything drawn on the canvas so far, strokes included.
+    fn content_bounds(&self) -> Option<Rect<T>>;
+
+    /// Set the view box to fit the content with `margin` on every side.
+    /// If an `aspect_ratio` (width / height) is given, the view box is extended
+    /// symmetrically along one axis to match it.
+    fn fit_view_box(self, margin: T, aspect_ratio: Option<T>) -> Self {
+        or_panic(self.try_fit_view_box(margin, aspect_ratio))
+    }
+
+    /// Fit the view box to the content, failing if `aspect_ratio` isn't positive and finite.
+    fn try_fit_view_box(self, margin: T, aspect_ratio: Option<T>) -> Result<Self>;
+
+    /// Print the canvas at `width` x `height` measured in `unit`.
+    /// The view box is mapped onto this size keeping its aspect ratio.
+    fn set_print_size(self, width: T, height: T, unit: Unit) -> Self;
+
+    /// Print the canvas such that one unit of the view box measures `scale` in `unit`.
+    /// The canvas is left untouched if it has no view box.
+    fn set_print_scale(self, scale: T, unit: Unit) -> Self;
+
+    /// Splice a copy of `motif` into the canvas, wrapped in a group transformed by `transform`.
+    /// The view box is grown to contain the transformed view box of `motif`.
+    fn splice(self, motif: &Self, transform: AffineTransform<T>) -> Self;
+
+    /// Splice `motif` once for every transform.
+    /// The motif is defined once as a `<symbol>` and placed with `<use>`.
+    fn splice_many(self, motif: &Self, transforms: Vec<AffineTransform<T>>) -> Self;
+
+    /// Splice `motif` scaled by `scale` and rotated by `alpha` degrees around the origin,
+    /// and then moved to `offset`.
+    fn splice_at(self, motif: &Self, offset: Point<T>, scale: T, alpha: T) -> Self {
+        let origin = Point::new(T::zero(), T::zero());
+        let transform = AffineTransform::translate(offset.x(), offset.y())
+            .compose(&AffineTransform::rotate(alpha, origin))
+            .compose(&AffineTransform::scale(scale, scale, origin));
+        self.splice(motif, transform)
+    }
+
+    /// Rotate the canvas by `alpha` degrees around `center`.
+    fn rotate(self, alpha: T, center: Point<T>) -> Self {
+        self.transform(AffineTransform::rotate(alpha, center))
+    }
+
+    /// Translate the canvas by `offset`.
+    fn translate(self, offset: Point<T>) -> Self {
+        self.transform(AffineTransform::translate(offset.x(), offset.y()))
+    }
+
+    /// Scale the canvas by `x_factor` and `y_factor` keeping `origin` fixed.
+    fn scale(self, x_factor: T, y_factor: T, origin: Point<T>) -> Self {
+        self.transform(AffineTransform::scale(x_factor, y_factor, origin))
+    }
+
+    /// Mirror the canvas along the line through `point` making an angle `alpha` with the x-axis.
+    fn mirror(self, point: Point<T>, alpha: T) -> Self {
+        let (sin, cos) = (alpha + alpha).to_radians().sin_cos();
+        let reflection = AffineTransform::new(cos, sin, T::zero(), sin, -cos, T::zero());
+        let transform = AffineTransform::translate(point.x(), point.y())
+            .compose(&reflection)
+            .compose(&AffineTransform::translate(-point.x(), -point.y()));
+        self.transform(transform)
+    }
+}
@@ -0,0 +1,465 @@
+use geo::AffineTransform;
+use geo::BoundingRect;
+use geo::Coord;
+use geo::LineString;
+use geo::Point;
+use geo::Rect;
+use geo_types::CoordFloat;
+use svg::node::element::path::Data;
+use svg::node::element::path::Parameters;
+use svg::node::element::{Circle, ClipPath, Group, Path, Rectangle, Symbol, Use};
+use svg::node::Value;
+use svg::Node;
+
+type Canvas = crate::canvas::SvgCanvas;
+
+use super::bounds::{add_svg_content_bounds, clip_svg_content_bounds, transformed_rect};
+use super::canvas::{Canvas as CanvasTrait, ClipShape, YAxis};
+use super::metadata::Unit;
+use crate::defs::definition_id;
+use crate::error::{Error, Result};
+use crate::path::segment::union;
+
+/// The map from canvas coordinates to svg coordinates, a flip for canvases with y pointing up.
+/// It is its own inverse.
+pub(crate) fn to_svg<T: CoordFloat>(y_axis: YAxis) -> AffineTransform<T> {
+    let zero = T::zero();
+    match y_axis {
+        YAxis::Up => AffineTransform::scale(T::one(), -T::one(), Coord { x: zero, y: zero }),
+        YAxis::Down => AffineTransform::identity(),
+    }
+}
+
+/// A point in canvas coordinates as a point in svg coordinates.
+pub(crate) fn svg_point<T: CoordFloat>(point: Point<T>, y_axis: YAxis) -> Point<T> {
+    match y_axis {
+        YAxis::Up => Point::new(point.x(), -point.y()),
+        YAxis::Down => point,
+    }
+}
+
+/// An affine transform given in the coordinates of a canvas with `y_axis` as a transform of svg coordinates.
+/// The canvas converts its coordinates to svg coordinates, so the transform is conjugated by this conversion.
+pub(crate) fn svg_transform<T: CoordFloat>(
+    transform: &AffineTransform<T>,
+    y_axis: YAxis,
+) -> AffineTransform {
+    let zero = T::zero();
+    let transform = to_svg(y_axis).compose(transform).compose(&to_svg(y_axis));
+    let apply = |x: T, y: T| transform.apply(Coord { x, y });
+    let offset = apply(zero, zero);
+    let x_column = apply(T::one(), zero) - offset;
+    let y_column = apply(zero, T::one()) - offset;
+    let [a, b, c, d, e, f] = [
+        x_column.x, x_column.y, y_column.x, y_column.y, offset.x, offset.y,
+    ]
+    .map(|entry| entry.to_f64().unwrap());
+    AffineTransform::new(a, c, e, b, d, f)
+}
+
+/// SVG `matrix(..)` of a transform of svg coordinates.
+fn matrix(transform: &AffineTransform) -> String {
+    let offset = transform.apply(Coord { x: 0.0, y: 0.0 });
+    let x_column = transform.apply(Coord { x: 1.0, y: 0.0 }) - offset;
+    let y_column = transform.apply(Coord { x: 0.0, y: 1.0 }) - offset;
+    let entries = [
+        x_column.x, x_column.y, y_column.x, y_column.y, offset.x, offset.y,
+    ]
+    .map(|entry| entry.to_string());
+    format!("matrix({})", entries.join(" "))
+}
+
+/// SVG `matrix(..)` for an affine transform given in the coordinates of a canvas with `y_axis`.
+pub(crate) fn svg_matrix<T: CoordFloat>(transform: &AffineTransform<T>, y_axis: YAxis) -> String {
+    matrix(&svg_transform(transform, y_axis))
+}
+
+/// The transform of svg coordinates placing the content of `motif` into `canvas` by `transform`,
+/// given from the coordinates of `motif` to the coordinates of `canvas`.
+fn motif_transform<T: CoordFloat>(
+    transform: &AffineTransform<T>,
+    canvas: &Canvas,
+    motif: &Canvas,
+) -> AffineTransform {
+    // svg_transform conjugates by the conversion of `canvas`, which is undone before converting `motif`
+    let transform = transform
+        .compose(&to_svg(canvas.y_axis))
+        .compose(&to_svg(motif.y_axis));
+    svg_transform(&transform, canvas.y_axis)
+}
+
+/// Bounds of the content of `motif` placed into `canvas` by each of `transforms`, in svg coordinates.
+fn motif_bounds<T: CoordFloat>(
+    transforms: &[AffineTransform<T>],
+    canvas: &Canvas,
+    motif: &Canvas,
+) -> Option<Rect<f64>> {
+    let rect = motif.bounds?;
+    transforms
+        .iter()
+        .map(|transform| transformed_rect(rect, &motif_transform(transform, canvas, motif)))
+        .reduce(union)
+}
+
+/// Bounds in svg coordinates as bounds in canvas coordinates.
+/// The conversion is its own inverse, so it also converts canvas bounds to svg bounds.
+pub(crate) fn from_svg_rect<T: CoordFloat>(rect: Rect<T>, y_axis: YAxis) -> Rect<T> {
+    let (min, max) = (rect.min(), rect.max());
+    match y_axis {
+        YAxis::Up => Rect::new(
+            Coord {
+                x: min.x,
+                y: -max.y,
+            },
+            Coord {
+                x: max.x,
+                y: -min.y,
+            },
+        ),
+        YAxis::Down => rect,
+    }
+}
+
+/// Add the definitions of `other` missing from the canvas, for content copied over from `other`.
+pub(crate) fn merge_definitions(canvas: Canvas, other: &Canvas) -> Canvas {
+    other
+        .definitions
+        .iter()
+        .fold(canvas, |canvas, (id, definition)| {
+            add_definition(canvas, id, definition.clone())
+        })
+}
+
+/// Add `definition` to the canvas unless one with the same `id` is already present.
+/// All definitions end up in one `<defs>` element, see [`SvgCanvas`](crate::canvas::SvgCanvas).
+pub(crate) fn add_definition<U>(mut canvas: Canvas, id: &str, definition: U) -> Canvas
+where
+    U: Into<Box<dyn Node>>,
+{
+    if !canvas.has_definition(id) {
+        canvas.definitions.push((id.to_string(), definition.into()));
+    }
+    canvas
+}
+
+/// The view box of the canvas as a rectangle in canvas coordinates.
+pub(crate) fn view_box<T: CoordFloat>(canvas: &Canvas) -> Option<Rect<T>> {
+    let view_box: Vec<T> = canvas
+        .get_attributes()
+        .get("viewBox")?
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| entry.parse::<f64>().ok().and_then(T::from))
+        .collect::<Option<_>>()?;
+    match view_box[..] {
+        [x, y, width, height] => Some(from_svg_rect(
+            Rect::new(
+                Coord { x, y },
+                Coord {
+                    x: x + width,
+                    y: y + height,
+                },
+            ),
+            canvas.y_axis,
+        )),
+        _ => None,
+    }
+}
+
+/// Set the view box of the canvas from a rectangle in canvas coordinates.
+pub(crate) fn set_view_box<T: CoordFloat>(canvas: Canvas, rect: Rect<T>) -> Canvas
+where
+    Value: From<T>,
+{
+    // the conversion from svg coordinates is its own inverse
+    let rect = from_svg_rect(rect, canvas.y_axis);
+    let view_box = (rect.min().x, rect.min().y, rect.width(), rect.height());
+    canvas.set("viewBox", view_box)
+}
+
+/// Grow the view box of `canvas` to contain the view box of `motif` mapped by `transforms`.
+fn merge_view_boxes<T: CoordFloat>(
+    canvas: Canvas,
+    motif: &Canvas,
+    transforms: &[AffineTransform<T>],
+) -> Canvas
+where
+    Value: From<T>,
+{
+    let motif_box = match view_box::<T>(motif) {
+        Some(motif_box) => motif_box,
+        None => return canvas,
+    };
+    let (min, max) = (motif_box.min(), motif_box.max());
+    let corners = [
+        min,
+        Coord { x: min.x, y: max.y },
+        max,
+        Coord { x: max.x, y: min.y },
+    ];
+    let merged = transforms
+        .iter()
+        .flat_map(|transform| corners.map(|corner| transform.apply(corner)))
+        .chain(
+            view_box::<T>(&canvas)
+                .into_iter()
+                .flat_map(|rect| [rect.min(), rect.max()]),
+        )
+        .map(|coord| Rect::new(coord, coord))
+        .reduce(|rect, other| {
+            Rect::new(
+                Coord {
+                    x: rect.min().x.min(other.min().x),
+                    y: rect.min().y.min(other.min().y),
+                },
+                Coord {
+                    x: rect.max().x.max(other.max().x),
+                    y: rect.max().y.max(other.max().y),
+                },
+            )
+        });
+    match merged {
+        Some(rect) => set_view_box(canvas, rect),
+        None => canvas,
+    }
+}
+
+/// A length with a unit, like `210mm`.
+fn length<T: CoordFloat>(value: T, unit: Unit) -> String {
+    format!("{}{}", value.to_f64().unwrap(), unit.suffix())
+}
+
+/// Path data for the closed rings of a polygon, in svg coordinates.
+fn rings_data<T: CoordFloat>(rings: Vec<&LineString<T>>, y_axis: YAxis) -> Data
+where
+    Parameters: From<T>,
+{
+    rings.into_iter().fold(Data::new(), |data, ring| {
+        let mut points = ring.points().map(|point| svg_point(point, y_axis));
+        match points.next() {
+            Some(start) => points
+                .fold(data.move_to(start.x_y()), |data, point| {
+                    data.line_to(point.x_y())
+                })
+                .close(),
+            None => data,
+        }
+    })
+}
+
+/// A `clipPath` holding `shape` given in the coordinates of a canvas with `y_axis`,
+/// identified by its content.
+fn clip_path<T: CoordFloat>(shape: ClipShape<T>, y_axis: YAxis) -> (String, ClipPath)
+where
+    Value: From<T>,
+    Parameters: From<T>,
+{
+    let clip_path = ClipPath::new();
+    let clip_path = match shape {
+        ClipShape::Rectangle(rect) => {
+            let rect = from_svg_rect(rect, y_axis);
+            let mut rectangle = Rectangle::new();
+            rectangle.assign("x", rect.min().x);
+            rectangle.assign("y", rect.min().y);
+            rectangle.assign("width", rect.width());
+            rectangle.assign("height", rect.height());
+            clip_path.add(rectangle)
+        }
+        ClipShape::Circle(radius, center) => {
+            let center = svg_point(center, y_axis);
+            let mut circle = Circle::new();
+            circle.assign("cx", center.x());
+            circle.assign("cy", center.y());
+            circle.assign("r", radius);
+            clip_path.add(circle)
+        }
+        ClipShape::Polygon(polygon) => {
+            let rings = std::iter::once(polygon.exterior())
+                .chain(polygon.interiors())
+                .collect();
+            let path = Path::new()
+                .set("d", rings_data(rings, y_axis))
+                .set("clip-rule", "evenodd");
+            clip_path.add(path)
+        }
+    };
+    let id = definition_id("clip", &clip_path);
+    (id.clone(), clip_path.set("id", id))
+}
+
+/// Bounds of a clip shape given in the coordinates of a canvas with `y_axis`, in svg coordinates.
+fn clip_bounds<T: CoordFloat>(shape: &ClipShape<T>, y_axis: YAxis) -> Option<Rect<f64>> {
+    let rect = match shape {
+        ClipShape::Rectangle(rect) => *rect,
+        ClipShape::Circle(radius, center) => {
+            let radius = Coord {
+                x: *radius,
+                y: *radius,
+            };
+            Rect::new(center.0 - radius, center.0 + radius)
+        }
+        ClipShape::Polygon(polygon) => polygon.bounding_rect()?,
+    };
+    let rect = from_svg_rect(rect, y_axis);
+    let coord = |coord: Coord<T>| {
+        Some(Coord {
+            x: coord.x.to_f64()?,
+            y: coord.y.to_f64()?,
+        })
+    };
+    Some(Rect::new(coord(rect.min())?, coord(rect.max())?))
+}
+
+/// Clip the children of `canvas` starting from index `start`.
+/// The recorded bounds of `canvas` are taken to be the bounds of these children.
+fn clip_from<T: CoordFloat>(mut canvas: Canvas, start: usize, shape: ClipShape<T>) -> Canvas
+where
+    Value: From<T>,
+    Parameters: From<T>,
+{
+    match clip_bounds(&shape, canvas.y_axis) {
+        Some(clip) => canvas = clip_svg_content_bounds(canvas, clip),
+        None => canvas.bounds = None,
+    };
+    let (id, clip_path) = clip_path(shape, canvas.y_axis);
+    let group = canvas
+        .get_children_mut()
+        .split_off(start)
+        .into_iter()
+        .fold(Group::new(), |group, child| group.add(child))
+        .set("clip-path", format!("url(#{})", id));
+    add_definition(canvas, &id, clip_path).add(group)
+}
+
+/*====== Canvas Implementation for svg documents ============ */
+impl<T: CoordFloat> CanvasTrait<T> for Canvas
+where
+    Value: From<T>,
+    Parameters: From<T>,
+{
+    fn with_view_box(view_box: Rect<T>, y_axis: YAxis) -> Self {
+        set_view_box(Canvas::with_y_axis(y_axis), view_box)
+    }
+
+    /// Wrap the current content into a transformed group.
+    fn transform(mut self, transform: AffineTransform<T>) -> Self {
+        let group = self
+            .get_children_mut()
+            .drain(..)
+            .fold(Group::new(), |group, child| group.add(child))
+            .set("transform", svg_matrix(&transform, self.y_axis));
+        let transform = svg_transform(&transform, self.y_axis);
+        self.bounds = self.bounds.map(|rect| transformed_rect(rect, &transform));
+        self.add(group)
+    }
+
+    fn clip(self, shape: ClipShape<T>) -> Self {
+        clip_from(self, 0, shape)
+    }
+
+    fn clip_with<F>(mut self, shape: ClipShape<T>, draw: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        let start = self.get_children().len();
+        // only the bounds of the content drawn by `draw` are clipped
+        let below = self.bounds.take();
+        let canvas = draw(self);
+        add_svg_content_bounds(clip_from(canvas, start, shape), below)
+    }
+
+    fn crop(self, rect: Rect<T>) -> Self {
+        set_view_box(self.clip(ClipShape::Rectangle(rect)), rect)
+    }
+
+    /// The bounds are recorded while drawing, see [`bounds`](super::bounds).
+    fn content_bounds(&self) -> Option<Rect<T>> {
+        let rect = self.bounds?;
+        let min = Coord {
+            x: T::from(rect.min().x)?,
+            y: T::from(rect.min().y)?,
+        };
+        let max = Coord {
+            x: T::from(rect.max().x)?,
+            y: T::from(rect.max().y)?,
+        };
+        Some(from_svg_rect(Rect::new(min, max), self.y_axis))
+    }
+
+    fn try_fit_view_box(self, margin: T, aspect_ratio: Option<T>) -> Result<Self> {
+        if aspect_ratio.is_some_and(|ratio| ratio <= T::zero() || !ratio.is_finite()) {
+            return Err(Error::InvalidAspectRatio);
+        }
+        let rect = match self.content_bounds() {
+            Some(rect) => rect,
+            None => return Ok(self),
+        };
+        let two = T::one() + T::one();
+        let margin = Coord {
+            x: margin,
+            y: margin,
+        };
+        let mut size = Coord {
+            x: rect.width(),
+            y: rect.height(),
+        } + margin * two;
+        match aspect_ratio {
+            Some(ratio) if size.x < size.y * ratio => size.x = size.y * ratio,
+            Some(ratio) if size.x > size.y * ratio => size.y = size.x / ratio,
+            _ => (),
+        };
+        let center = rect.center();
+        Ok(set_view_box(
+            self,
+            Rect::new(center - size / two, center + size / two),
+        ))
+    }
+
+    fn set_print_size(self, width: T, height: T, unit: Unit) -> Self {
+        self.set("width", length(width, unit))
+            .set("height", length(height, unit))
+    }
+
+    fn set_print_scale(self, scale: T, unit: Unit) -> Self {
+        match view_box::<T>(&self) {
+            Some(rect) => self.set_print_size(rect.width() * scale, rect.height() * scale, unit),
+            None => self,
+        }
+    }
+
+    fn splice(self, motif: &Self, transform: AffineTransform<T>) -> Self {
+        let group = motif
+            .get_children()
+            .iter()
+            .fold(Group::new(), |group, child| group.add(child.clone()))
+            .set(
+                "transform",
+                matrix(&motif_transform(&transform, &self, motif)),
+            );
+        let bounds = motif_bounds(&[transform], &self, motif);
+        let canvas = add_svg_content_bounds(merge_definitions(self, motif), bounds);
+        merge_view_boxes(canvas, motif, &[transform]).add(group)
+    }
+
+    /// The symbol doesn't clip its content, which may lie anywhere around the origin of the motif.
+    fn splice_many(self, motif: &Self, transforms: Vec<AffineTransform<T>>) -> Self {
+        let symbol = motif
+            .get_children()
+            .iter()
+            .fold(Symbol::new(), |symbol, child| symbol.add(child.clone()))
+            .set("overflow", "visible");
+        let id = definition_id("motif", &symbol);
+        let bounds = motif_bounds(&transforms, &self, motif);
+        let canvas = add_svg_content_bounds(merge_definitions(self, motif), bounds);
+        let canvas = add_definition(canvas, &id, symbol.set("id", id.clone()));
+        let canvas = merge_view_boxes(canvas, motif, &transforms);
+        transforms.iter().fold(canvas, |canvas, transform| {
+            let transform = motif_transform(transform, &canvas, motif);
+            canvas.add(
+                Use::new()
+                    .set("href", format!("#{}", id))
+                    .set("transform", matrix(&transform)),
+            )
+        })
+    }
+}
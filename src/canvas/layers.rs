@@ -0,0 +1,172 @@
+//! Named layers on top of a canvas.
+//! Every layer is rendered as a `<g id=...>` group, in the order of the layers.
+use geo::Point;
+use geo_types::CoordFloat;
+use svg::node::element::Group;
+
+use crate::art_forms::base_shapes::Config;
+use crate::backend::Backend;
+use crate::path::Path;
+use crate::scene::Shape;
+
+use super::bounds::add_svg_content_bounds;
+use super::canvas_impl::merge_definitions;
+
+type Canvas = crate::canvas::SvgCanvas;
+
+#[derive(Clone)]
+struct Layer {
+    name: String,
+    content: Canvas,
+    hidden: bool,
+}
+
+/// A canvas whose content is organised in named layers.
+/// Drawing goes to the currently selected layer, see [`LayeredCanvas::layer`].
+/// The first layer is at the bottom.
+#[derive(Clone)]
+pub struct LayeredCanvas {
+    canvas: Canvas,
+    layers: Vec<Layer>,
+    current: usize,
+}
+
+impl LayeredCanvas {
+    /// Create a layered canvas on top of `canvas`.
+    /// Content already drawn on `canvas` stays below all layers.
+    pub fn new(canvas: Canvas) -> Self {
+        LayeredCanvas {
+            canvas,
+            layers: vec![],
+            current: 0,
+        }
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|layer| layer.name == name)
+    }
+
+    /// Select the layer `name` for drawing, creating it on top if it doesn't exist yet.
+    pub fn layer(mut self, name: &str) -> Self {
+        self.current = match self.position(name) {
+            Some(index) => index,
+            None => {
+                self.layers.push(Layer {
+                    name: name.to_string(),
+                    content: Canvas::with_y_axis(self.canvas.y_axis),
+                    hidden: false,
+                });
+                self.layers.len() - 1
+            }
+        };
+        self
+    }
+
+    /// Names of all layers from bottom to top.
+    pub fn layer_names(&self) -> Vec<&str> {
+        self.layers
+            .iter()
+            .map(|layer| layer.name.as_str())
+            .collect()
+    }
+
+    /// Move the layer `name` to `index` in the stack, `0` being the bottom.
+    /// Indices past the top move the layer to the top.
+    pub fn move_layer(mut self, name: &str, index: usize) -> Self {
+        if let Some(from) = self.position(name) {
+            let current = self.layers[self.current].name.clone();
+            let layer = self.layers.remove(from);
+            let index = index.min(self.layers.len());
+            self.layers.insert(index, layer);
+            self.current = self.position(&current).unwrap_or(0);
+        }
+        self
+    }
+
+    /// Hide the layer `name`, it is kept with `display="none"`.
+    pub fn hide_layer(self, name: &str) -> Self {
+        self.set_hidden(name, true)
+    }
+
+    /// Show the layer `name` again.
+    pub fn show_layer(self, name: &str) -> Self {
+        self.set_hidden(name, false)
+    }
+
+    fn set_hidden(mut self, name: &str, hidden: bool) -> Self {
+        if let Some(index) = self.position(name) {
+            self.layers[index].hidden = hidden;
+        }
+        self
+    }
+
+    /// Remove the layer `name` along with everything drawn on it.
+    /// If it was selected, the top layer gets selected.
+    pub fn remove_layer(mut self, name: &str) -> Self {
+        if let Some(index) = self.position(name) {
+            let current = self.layers[self.current].name.clone();
+            self.layers.remove(index);
+            self.current = self
+                .position(&current)
+                .unwrap_or(self.layers.len().saturating_sub(1));
+        }
+        self
+    }
+
+    /// Draw on the selected layer, selecting a default layer if there is none.
+    fn draw<F>(self, draw: F) -> Self
+    where
+        F: FnOnce(Canvas) -> Canvas,
+    {
+        let mut layered = match self.layers.is_empty() {
+            true => self.layer("default"),
+            false => self,
+        };
+        let layer = &mut layered.layers[layered.current];
+        let content = std::mem::replace(&mut layer.content, Canvas::new());
+        layer.content = draw(content);
+        layered
+    }
+
+    /// Render the layers as groups onto the underlying canvas.
+    pub fn into_canvas(self) -> Canvas {
+        self.layers
+            .into_iter()
+            .fold(self.canvas, |canvas, mut layer| {
+                let canvas = merge_definitions(canvas, &layer.content);
+                let canvas = match layer.hidden {
+                    true => canvas,
+                    false => add_svg_content_bounds(canvas, layer.content.bounds),
+                };
+                let group = layer
+                    .content
+                    .get_children_mut()
+                    .drain(..)
+                    .fold(Group::new().set("id", layer.name), |group, child| {
+                        group.add(child)
+                    });
+                canvas.add(match layer.hidden {
+                    true => group.set("display", "none"),
+                    false => group,
+                })
+            })
+    }
+}
+
+/*====== Drawing on the selected layer ============ */
+impl<T: CoordFloat> Backend<T> for LayeredCanvas
+where
+    Canvas: Backend<T>,
+{
+    fn draw_circle(self, radius: T, center: Point<T>, config: Config<T>) -> Self {
+        self.draw(|canvas| canvas.draw_circle(radius, center, config))
+    }
+
+    fn draw_path(self, path: Path<T>, config: Config<T>) -> Self {
+        self.draw(|canvas| canvas.draw_path(path, config))
+    }
+
+    fn draw_shape(self, shape: &Shape<T>) -> Self {
+        self.draw(|canvas| canvas.draw_shape(shape))
+    }
+}
@@ -0,0 +1,199 @@
+//! Physical units and document metadata of a canvas.
+use derive_new::new;
+use svg::node::element::Element;
+use svg::node::{Node, Text};
+
+type Canvas = crate::canvas::SvgCanvas;
+
+/// Units for the physical size of a canvas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unit {
+    Millimeter,
+    Centimeter,
+    Inch,
+    Pixel,
+}
+
+impl Unit {
+    /// Unit suffix as used in svg lengths.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Unit::Millimeter => "mm",
+            Unit::Centimeter => "cm",
+            Unit::Inch => "in",
+            Unit::Pixel => "px",
+        }
+    }
+}
+
+/// [Dublin Core](https://www.dublincore.org/specifications/dublin-core/dcmi-terms/)
+/// metadata of a canvas, written as RDF into the `<metadata>` of the svg.
+/// Only the fields that are set are written.
+#[derive(Clone, Debug, Default, PartialEq, new)]
+pub struct Metadata {
+    #[new(default)]
+    pub title: Option<String>,
+    #[new(default)]
+    pub creator: Option<String>,
+    #[new(default)]
+    pub description: Option<String>,
+    #[new(default)]
+    pub date: Option<String>,
+    #[new(default)]
+    pub rights: Option<String>,
+    /// Url of the license, e.g. `https://creativecommons.org/licenses/by/4.0/`.
+    #[new(default)]
+    pub license: Option<String>,
+    #[new(default)]
+    pub keywords: Vec<String>,
+}
+
+macro_rules! implement_metadata_setter(
+    ($field:ident) => (
+        pub fn $field(mut self, $field: &str) -> Self {
+            self.$field = Some($field.to_string());
+            self
+        }
+    );
+);
+
+impl Metadata {
+    implement_metadata_setter! {title}
+    implement_metadata_setter! {creator}
+    implement_metadata_setter! {description}
+    implement_metadata_setter! {date}
+    implement_metadata_setter! {rights}
+    implement_metadata_setter! {license}
+
+    pub fn keyword(mut self, keyword: &str) -> Self {
+        self.keywords.push(keyword.to_string());
+        self
+    }
+
+    /// The `<metadata>` element holding the RDF description.
+    pub(crate) fn to_element(&self) -> Element {
+        let mut work = Element::new("cc:Work");
+        work.assign("rdf:about", "");
+        work.append(text_element("dc:format", "image/svg+xml"));
+        let mut kind = Element::new("dc:type");
+        kind.assign("rdf:resource", "http://purl.org/dc/dcmitype/StillImage");
+        work.append(kind);
+        if let Some(title) = &self.title {
+            work.append(text_element("dc:title", title));
+        }
+        if let Some(creator) = &self.creator {
+            let mut agent = Element::new("cc:Agent");
+            agent.append(text_element("dc:title", creator));
+            let mut element = Element::new("dc:creator");
+            element.append(agent);
+            work.append(element);
+        }
+        if let Some(description) = &self.description {
+            work.append(text_element("dc:description", description));
+        }
+        if let Some(date) = &self.date {
+            work.append(text_element("dc:date", date));
+        }
+        if let Some(rights) = &self.rights {
+            let mut agent = Element::new("cc:Agent");
+            agent.append(text_element("dc:title", rights));
+            let mut element = Element::new("dc:rights");
+            element.append(agent);
+            work.append(element);
+        }
+        if let Some(license) = &self.license {
+            let mut element = Element::new("cc:license");
+            element.assign("rdf:resource", escape(license));
+            work.append(element);
+        }
+        if !self.keywords.is_empty() {
+            let bag = self
+                .keywords
+                .iter()
+                .fold(Element::new("rdf:Bag"), |mut bag, keyword| {
+                    bag.append(text_element("rdf:li", keyword));
+                    bag
+                });
+            let mut subject = Element::new("dc:subject");
+            subject.append(bag);
+            work.append(subject);
+        }
+
+        let mut rdf = Element::new("rdf:RDF");
+        rdf.assign("xmlns:rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#");
+        rdf.assign("xmlns:dc", "http://purl.org/dc/elements/1.1/");
+        rdf.assign("xmlns:cc", "http://creativecommons.org/ns#");
+        rdf.append(work);
+        let mut metadata = Element::new("metadata");
+        metadata.append(rdf);
+        metadata
+    }
+}
+
+/// Escape the xml special characters of a text.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// An element holding escaped text, like `<dc:title>..</dc:title>`.
+pub(crate) fn text_element(name: &str, text: &str) -> Element {
+    let mut element = Element::new(name);
+    element.append(Text::new(escape(text)));
+    element
+}
+
+/// Title, description and metadata of a canvas.
+pub trait Annotate: Sized {
+    /// Set the `<title>` of the canvas.
+    fn set_title(self, title: &str) -> Self;
+
+    /// Set the `<desc>` of the canvas.
+    fn set_description(self, description: &str) -> Self;
+
+    /// Set the RDF `<metadata>` of the canvas.
+    fn set_metadata(self, metadata: Metadata) -> Self;
+}
+
+/// Tag name of a node, like `title` for `<title>..</title>`.
+pub(crate) fn tag_name(node: &dyn Node) -> String {
+    node.to_string()
+        .trim_start_matches('<')
+        .chars()
+        .take_while(|c| !c.is_whitespace() && *c != '>' && *c != '/')
+        .collect()
+}
+
+/// Put `element` at the head of the canvas, replacing an earlier element with the same tag.
+/// The head is kept in the order title, desc, metadata.
+fn set_head_element(mut canvas: Canvas, tag: &str, element: Element) -> Canvas {
+    let order = ["title", "desc", "metadata"];
+    let rank = |tag: &str| order.iter().position(|other| *other == tag);
+    let children = canvas.get_children_mut();
+    children.retain(|child| tag_name(child.as_ref()) != tag);
+    let index = children
+        .iter()
+        .take_while(|child| match (rank(&tag_name(child.as_ref())), rank(tag)) {
+            (Some(child), Some(tag)) => child < tag,
+            _ => false,
+        })
+        .count();
+    children.insert(index, Box::new(element));
+    canvas
+}
+
+impl Annotate for Canvas {
+    fn set_title(self, title: &str) -> Self {
+        set_head_element(self, "title", text_element("title", title))
+    }
+
+    fn set_description(self, description: &str) -> Self {
+        set_head_element(self, "desc", text_element("desc", description))
+    }
+
+    fn set_metadata(self, metadata: Metadata) -> Self {
+        set_head_element(self, "metadata", metadata.to_element())
+    }
+}
@@ -1 +1,14 @@
+#[allow(clippy::module_inception)]
 pub mod canvas;
+pub use canvas::{Canvas, ClipShape, YAxis};
+pub(crate) mod bounds;
+pub mod canvas_impl;
+pub mod layers;
+pub use layers::LayeredCanvas;
+pub mod metadata;
+pub use metadata::{Annotate, Metadata, Unit};
+pub mod svg_canvas;
+pub use svg_canvas::SvgCanvas;
+
+#[cfg(test)]
+pub mod test_canvas;
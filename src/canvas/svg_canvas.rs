@@ -0,0 +1,160 @@
+//! The svg document a canvas draws on, along with the state the canvas keeps while drawing.
+//! The state lives next to the document and is never written into it,
+//! so saved files hold nothing but the drawing.
+//! Definitions are kept apart as well and written in a single `<defs>` element
+//! when the canvas is displayed or saved.
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use geo::Rect;
+use svg::node::element::Definitions;
+use svg::node::{NodeDefaultHash, Value};
+use svg::{Document, Node};
+
+use super::canvas::YAxis;
+use super::metadata::tag_name;
+
+/// An svg document with the direction of its y-axis, its definitions
+/// and the bounds of its content.
+/// It derefs to the [`Document`] of the content drawn, without the definitions.
+/// It is displayed as the complete document and can be saved with [`svg::save`] like one.
+#[derive(Clone, Debug)]
+pub struct SvgCanvas {
+    document: Document,
+    /// Direction of the y-axis of the coordinates drawn in.
+    pub(crate) y_axis: YAxis,
+    /// Definitions used by the content with their ids, each is defined once.
+    pub(crate) definitions: Vec<(String, Box<dyn Node>)>,
+    /// Bounds of the content drawn so far in svg coordinates, strokes included and clips respected.
+    pub(crate) bounds: Option<Rect>,
+}
+
+impl Default for SvgCanvas {
+    fn default() -> Self {
+        Document::new().into()
+    }
+}
+
+impl SvgCanvas {
+    /// An empty canvas with its y-axis pointing up.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An empty canvas with its y-axis pointing in the direction of `y_axis`.
+    pub(crate) fn with_y_axis(y_axis: YAxis) -> Self {
+        SvgCanvas {
+            y_axis,
+            ..Self::new()
+        }
+    }
+
+    /// Set an attribute of the document, like its `viewBox`.
+    pub fn set<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<String>,
+        V: Into<Value>,
+    {
+        self.document.assign(name, value);
+        self
+    }
+
+    /// Append a node to the document.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<N>(mut self, node: N) -> Self
+    where
+        N: Into<Box<dyn Node>>,
+    {
+        self.document.append(node);
+        self
+    }
+
+    /// The document drawn so far with its definitions.
+    pub fn to_document(&self) -> Document {
+        self.clone().into_document()
+    }
+
+    /// The document drawn so far with its definitions, dropping the state of the canvas.
+    /// The definitions follow the title, description and metadata of the document.
+    pub fn into_document(self) -> Document {
+        let mut document = self.document;
+        if self.definitions.is_empty() {
+            return document;
+        }
+        let definitions = self
+            .definitions
+            .into_iter()
+            .fold(Definitions::new(), |definitions, (_, definition)| {
+                definitions.add(definition)
+            });
+        let children = document.get_children_mut();
+        let head = ["title", "desc", "metadata"];
+        let index = children
+            .iter()
+            .take_while(|child| head.contains(&tag_name(child.as_ref()).as_str()))
+            .count();
+        children.insert(index, Box::new(definitions));
+        document
+    }
+
+    /// Whether a definition with `id` has been added.
+    pub(crate) fn has_definition(&self, id: &str) -> bool {
+        self.definitions.iter().any(|(defined, _)| defined == id)
+    }
+}
+
+/// A canvas drawing on an existing document, with its y-axis pointing up.
+impl From<Document> for SvgCanvas {
+    fn from(document: Document) -> Self {
+        SvgCanvas {
+            document,
+            y_axis: YAxis::Up,
+            definitions: vec![],
+            bounds: None,
+        }
+    }
+}
+
+impl Deref for SvgCanvas {
+    type Target = Document;
+
+    fn deref(&self) -> &Document {
+        &self.document
+    }
+}
+
+impl DerefMut for SvgCanvas {
+    fn deref_mut(&mut self) -> &mut Document {
+        &mut self.document
+    }
+}
+
+impl fmt::Display for SvgCanvas {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_document().fmt(formatter)
+    }
+}
+
+impl Node for SvgCanvas {
+    fn append<N>(&mut self, node: N)
+    where
+        N: Into<Box<dyn Node>>,
+    {
+        self.document.append(node);
+    }
+
+    fn assign<N, V>(&mut self, name: N, value: V)
+    where
+        N: Into<String>,
+        V: Into<Value>,
+    {
+        self.document.assign(name, value);
+    }
+}
+
+impl NodeDefaultHash for SvgCanvas {
+    fn default_hash(&self, state: &mut DefaultHasher) {
+        self.to_document().default_hash(state);
+    }
+}
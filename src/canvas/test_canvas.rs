@@ -0,0 +1,470 @@
+use super::Canvas as _;
+use super::{Annotate, ClipShape, LayeredCanvas, Metadata, Unit, YAxis};
+use crate::art_forms::base_shapes::{BaseShapes, Config};
+use crate::art_forms::leaves::{LeafStyle, Leaves};
+use crate::art_forms::yantra::Yantra;
+use crate::error::Error;
+
+use crate::canvas::SvgCanvas;
+use geo::{point, polygon, AffineTransform, Rect};
+
+type Canvas = SvgCanvas;
+type T = f64;
+
+fn sri_canvas(view_size: T) -> Canvas {
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let center = point!(x: 0.0, y: 0.0);
+    let outer = Config::<T>::try_new(1.0, "blue", "blue").unwrap();
+    let inner = Config::<T>::try_new(1.0, "red", "red").unwrap();
+    let configs = [0, 1, 2, 3, 4, 5, 6, 7, 8].map(|i| match i % 2 {
+        0 => outer.clone(),
+        _ => inner.clone(),
+    });
+    canvas.add_sri(100.0, center, configs)
+}
+
+#[test]
+fn test_rotate() {
+    let canvas = sri_canvas(150.0).rotate(90.0, point!(x: 0.0, y: 0.0));
+    svg::save("./unit_tests/canvas/rotate.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_translate_and_scale() {
+    let canvas = sri_canvas(150.0)
+        .scale(0.5, 0.5, point!(x: 0.0, y: 0.0))
+        .translate(point!(x: 50.0, y: 50.0));
+    svg::save("./unit_tests/canvas/translate_and_scale.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_mirror() {
+    let config = Config::<T>::try_new(1.0, "blue", "yellow").unwrap();
+    let canvas = sri_canvas(150.0)
+        .add_regular_n_gon(20.0, point!(x: 80.0, y: 0.0), 0.0, 3, config)
+        .mirror(point!(x: 0.0, y: 0.0), 90.0);
+    svg::save("./unit_tests/canvas/mirror.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_svg_matrix() {
+    use super::canvas_impl::svg_matrix;
+    // A counter-clockwise quarter turn in y-up coordinates is clockwise in svg coordinates.
+    let rotation = AffineTransform::<T>::rotate(90.0, point!(x: 0.0, y: 0.0));
+    let matrix = svg_matrix(&rotation, YAxis::Up);
+    let entries: Vec<T> = matrix["matrix(".len()..matrix.len() - 1]
+        .split(' ')
+        .map(|entry| entry.parse().unwrap())
+        .collect();
+    let expected = [0.0, -1.0, 1.0, 0.0, 0.0, 0.0];
+    for (entry, expected) in entries.iter().zip(expected) {
+        assert!((entry - expected).abs() < 1e-9);
+    }
+    // Translations flip their y-component.
+    let translation = AffineTransform::<T>::translate(3.0, 4.0);
+    assert_eq!(svg_matrix(&translation, YAxis::Up), "matrix(1 0 0 1 3 -4)");
+    // Canvases with y pointing down use svg coordinates as they are.
+    assert_eq!(svg_matrix(&translation, YAxis::Down), "matrix(1 0 0 1 3 4)");
+}
+
+#[test]
+fn test_clip_with_circle() {
+    let view_size = 150.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let center = point!(x: 0.0, y: 0.0);
+    let radius = 100.0;
+    let config = Config::<T>::try_new(1.0, "blue", "yellow").unwrap();
+    let reniform_style = LeafStyle::<T>::new_reniform(25.0, 1.1, 12.0, 13.0);
+    // trim the leaf tips to a circular frame, the circle drawn first is not clipped
+    let canvas = canvas
+        .add_circle(radius + 20.0, center, config.clone())
+        .clip_with(ClipShape::Circle(radius + 15.0, center), |canvas| {
+            canvas.add_circular_leaves(radius, center, 0.0, 16, 1.0, reniform_style, config)
+        });
+    svg::save("./unit_tests/canvas/clip_with_circle.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_clip_to_polygon_and_crop() {
+    let triangle = polygon![
+        (x: -100.0, y: -60.0),
+        (x: 100.0, y: -60.0),
+        (x: 0.0, y: 110.0),
+    ];
+    let canvas = sri_canvas(150.0)
+        .clip(ClipShape::Polygon(triangle))
+        .crop(Rect::new(
+            point!(x: -100.0, y: -50.0),
+            point!(x: 100.0, y: 100.0),
+        ));
+    let content = canvas.to_string();
+    assert_eq!(content.matches("<clipPath").count(), 2);
+    assert!(content.contains(r#"viewBox="-100 -100 200 150""#));
+    svg::save("./unit_tests/canvas/clip_to_polygon_and_crop.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_splice() {
+    let view_size = 30.0;
+    let star: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let config = Config::<T>::try_new(1.0, "blue", "yellow").unwrap();
+    let star = star.add_isotoxal_star(25.0, point!(x: 0.0, y: 0.0), 90.0, 5, 2, config, None);
+
+    // the view box of the host grows to contain the motif
+    let canvas = sri_canvas(100.0).splice_at(&star, point!(x: 120.0, y: 0.0), 1.0, 0.0);
+    assert_eq!(
+        super::canvas_impl::view_box::<T>(&canvas),
+        Some(Rect::new(
+            point!(x: -100.0, y: -100.0),
+            point!(x: 150.0, y: 100.0),
+        ))
+    );
+
+    // repeated motifs share a single symbol
+    let transforms = (0..8)
+        .map(|i| {
+            AffineTransform::rotate(45.0 * i as T, point!(x: 0.0, y: 0.0))
+                .compose(&AffineTransform::translate(0.0, 120.0))
+                .compose(&AffineTransform::scale(0.5, 0.5, point!(x: 0.0, y: 0.0)))
+        })
+        .collect::<Vec<_>>();
+    let canvas = canvas
+        .splice_many(&star, transforms.clone())
+        .splice_many(&star, transforms);
+    let content = canvas.to_string();
+    assert_eq!(content.matches("<symbol").count(), 1);
+    assert_eq!(content.matches("<use").count(), 16);
+    svg::save("./unit_tests/canvas/splice.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_splice_many_keeps_content_around_the_origin() {
+    // the motif lies on both sides of its origin, none of it may be clipped by the symbol
+    let view_box = Rect::new(point!(x: -20.0, y: -20.0), point!(x: 20.0, y: 20.0));
+    let config = Config::<T>::try_new(2.0, "blue", "yellow").unwrap();
+    let motif =
+        Canvas::with_view_box(view_box, YAxis::Up).add_circle(10.0, point!(x: 0.0, y: 0.0), config);
+    let transforms = vec![
+        AffineTransform::translate(100.0, 0.0),
+        AffineTransform::translate(-100.0, 50.0),
+    ];
+    let canvas = Canvas::with_view_box(view_box, YAxis::Up).splice_many(&motif, transforms);
+    assert!(canvas.to_string().contains(r#"overflow="visible""#));
+    assert_eq!(
+        canvas.content_bounds(),
+        Some(Rect::new(
+            point!(x: -111.0, y: -11.0),
+            point!(x: 111.0, y: 61.0),
+        ))
+    );
+}
+
+#[test]
+fn test_layers() {
+    let view_size = 150.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let center = point!(x: 0.0, y: 0.0);
+    let outer = Config::<T>::try_new(1.0, "blue", "blue").unwrap();
+    let inner = Config::<T>::try_new(1.0, "red", "red").unwrap();
+    let configs = [0, 1, 2, 3, 4, 5, 6, 7, 8].map(|i| match i % 2 {
+        0 => outer.clone(),
+        _ => inner.clone(),
+    });
+    let background = Config::<T>::try_new(1.0, "none", "#E8FB62").unwrap();
+    let grid = Config::<T>::try_new(0.5, "gray", "none").unwrap();
+
+    // the background is drawn last but rendered below the yantra
+    let layered = LayeredCanvas::new(canvas)
+        .layer("background")
+        .layer("yantra")
+        .add_sri(100.0, center, configs)
+        .layer("grid")
+        .add_circles(1.0, vec![center], grid)
+        .layer("background")
+        .add_circle(120.0, center, background)
+        .layer("guides")
+        .move_layer("guides", 0)
+        .hide_layer("grid")
+        .remove_layer("guides");
+    assert_eq!(layered.layer_names(), vec!["background", "yantra", "grid"]);
+
+    let canvas = layered.into_canvas();
+    let content = canvas.to_string();
+    let background_at = content.find(r#"<g id="background">"#).unwrap();
+    let yantra_at = content.find(r#"<g id="yantra">"#).unwrap();
+    assert!(background_at < yantra_at);
+    assert!(content.contains(r#"<g display="none" id="grid">"#));
+    svg::save("./unit_tests/canvas/layers.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_content_bounds() {
+    let config = Config::<T>::try_new(2.0, "blue", "yellow").unwrap();
+    let canvas = Canvas::new().add_circle(50.0, point!(x: 10.0, y: 20.0), config.clone());
+    let expected = Rect::new(point!(x: -41.0, y: -31.0), point!(x: 61.0, y: 71.0));
+    assert_eq!(canvas.content_bounds(), Some(expected));
+
+    // transforms and clips are followed
+    let canvas = canvas
+        .rotate(90.0, point!(x: 10.0, y: 20.0))
+        .clip(ClipShape::Rectangle(Rect::new(
+            point!(x: 0.0, y: 0.0),
+            point!(x: 100.0, y: 100.0),
+        )));
+    let bounds: Rect<T> = canvas.content_bounds().unwrap();
+    assert!((bounds.min().x - 0.0).abs() < 1e-4);
+    assert!((bounds.max().y - 71.0).abs() < 1e-4);
+
+    // the bulge of a cubic leaf outline is included
+    let reniform_style = LeafStyle::<T>::new_reniform(40.0, 1.0, 24.0, 20.0);
+    let canvas = Canvas::new().add_single_circular_leaf(
+        100.0,
+        point!(x: 0.0, y: 0.0),
+        -20.0,
+        20.0,
+        reniform_style,
+        Config::try_new(0.0, "none", "green").unwrap(),
+    );
+    let bounds: Rect<T> = canvas.content_bounds().unwrap();
+    assert!((bounds.max().x - 140.0).abs() < 1e-4);
+    assert!(bounds.max().y > 100.0 * 20.0_f64.to_radians().sin() + 1.0);
+}
+
+#[test]
+fn test_fit_view_box() {
+    let config = Config::<T>::try_new(1.5, "blue", "yellow").unwrap();
+    let cordate_style = LeafStyle::<T>::new_cordate(40.0, 1.0, 62.0, 28.0);
+    let center = point!(x: 0.0, y: 0.0);
+    let canvas = Canvas::new()
+        .add_isotoxal_star(
+            100.0,
+            point!(x: 200.0, y: 0.0),
+            0.0,
+            13,
+            3,
+            config.clone(),
+            None,
+        )
+        .add_circle(100.0, center, config.clone())
+        .add_circular_leaves(100.0, center, 0.0, 10, -1.0, cordate_style, config)
+        .fit_view_box(10.0, Some(2.0));
+    let view_box: Rect<T> = super::canvas_impl::view_box(&canvas).unwrap();
+    let content: Rect<T> = canvas.content_bounds().unwrap();
+    assert!((view_box.width() - 2.0 * view_box.height()).abs() < 1e-6);
+    // the content is narrower than 2:1, so the width is extended
+    assert!((view_box.height() - content.height() - 20.0).abs() < 1e-6);
+    assert!(view_box.min().x < content.min().x - 10.0);
+    assert_eq!(view_box.center(), content.center());
+    svg::save("./unit_tests/canvas/fit_view_box.svg", &canvas).unwrap();
+
+    for ratio in [0.0, -1.0, T::NAN, T::INFINITY] {
+        assert_eq!(
+            canvas.clone().try_fit_view_box(10.0, Some(ratio)).err(),
+            Some(Error::InvalidAspectRatio)
+        );
+    }
+}
+
+#[test]
+fn test_print_size_and_metadata() {
+    let metadata = Metadata::new()
+        .title("Sri Yantra")
+        .creator("rust-svg")
+        .license("https://creativecommons.org/licenses/by/4.0/")
+        .keyword("yantra")
+        .keyword("mandala");
+    let canvas = sri_canvas(100.0)
+        .set_print_scale(0.5, Unit::Millimeter)
+        .set_metadata(metadata)
+        .set_description("Nine interlocking triangles <drawn> & coloured")
+        .set_title("A draft title")
+        .set_title("Sri Yantra");
+    let content = canvas.to_string();
+    assert!(content.contains(r#"width="100mm""#));
+    assert!(content.contains(r#"height="100mm""#));
+    assert_eq!(content.matches("<title>").count(), 1);
+    assert!(content.contains("&lt;drawn&gt; &amp; coloured"));
+    // title, desc and metadata come first, in this order
+    let title_at = content.find("<title>").unwrap();
+    let desc_at = content.find("<desc>").unwrap();
+    let metadata_at = content.find("<metadata>").unwrap();
+    assert!(title_at < desc_at && desc_at < metadata_at);
+    assert!(metadata_at < content.find("<path").unwrap());
+    assert!(content
+        .contains(r#"<cc:license rdf:resource="https://creativecommons.org/licenses/by/4.0/"/>"#));
+
+    let canvas = canvas.set_print_size(8.0, 8.0, Unit::Inch);
+    assert!(canvas.to_string().contains(r#"width="8in""#));
+    svg::save("./unit_tests/canvas/print_size_and_metadata.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_y_axis() {
+    let view_box = Rect::new(point!(x: 0.0, y: 0.0), point!(x: 100.0, y: 50.0));
+    let config = Config::<T>::try_new(1.0, "blue", "yellow").unwrap();
+    let draw = |canvas: Canvas| {
+        canvas
+            .add_circle(5.0, point!(x: 10.0, y: 20.0), config.clone())
+            .add_polygon(
+                vec![
+                    point!(x: 30.0, y: 10.0),
+                    point!(x: 40.0, y: 10.0),
+                    point!(x: 35.0, y: 30.0),
+                ],
+                config.clone(),
+            )
+    };
+
+    // Elements carry no implicit transforms, the coordinates are converted instead.
+    let up = draw(Canvas::with_view_box(view_box, YAxis::Up));
+    let content = up.to_string();
+    assert!(!content.contains("transform"));
+    assert!(content.contains(r#"viewBox="0 -50 100 50""#));
+    assert!(content.contains(r#"cy="-20""#));
+    assert!(content.contains("M30,-10 L40,-10 L35,-30 z"));
+
+    let down = draw(Canvas::with_view_box(view_box, YAxis::Down));
+    let content = down.to_string();
+    assert!(!content.contains("transform"));
+    assert!(content.contains(r#"viewBox="0 0 100 50""#));
+    assert!(content.contains(r#"cy="20""#));
+    assert!(content.contains("M30,10 L40,10 L35,30 z"));
+
+    // Both conventions agree on the view box and the bounds in their own coordinates.
+    for canvas in [&up, &down] {
+        assert_eq!(super::canvas_impl::view_box::<T>(canvas), Some(view_box));
+        let bounds: Rect<T> = canvas.content_bounds().unwrap();
+        assert_eq!(bounds.min(), point!(x: 4.5, y: 9.5).into());
+    }
+    svg::save("./unit_tests/canvas/y_axis_down.svg", &down).unwrap();
+
+    // Splicing maps the coordinates of a motif to the coordinates of the canvas,
+    // whatever the conventions of both.
+    let spliced =
+        Canvas::with_view_box(view_box, YAxis::Up).splice(&down, AffineTransform::<T>::identity());
+    let bounds: Option<Rect<T>> = down.content_bounds();
+    assert_eq!(spliced.content_bounds(), bounds);
+}
+
+#[test]
+fn test_saved_files_hold_no_canvas_state() {
+    use crate::color::Color;
+    use crate::paint::LinearGradient;
+    let gradient = LinearGradient::new(point!(x: -50.0, y: 0.0), point!(x: 50.0, y: 0.0))
+        .stop(0.0, Color::WHITE)
+        .stop(1.0, Color::rgb(255, 0, 0));
+    let config = Config::<T>::from_paints(1.0, Color::BLACK, gradient);
+    let view_box = Rect::new(point!(x: -100.0, y: -100.0), point!(x: 100.0, y: 100.0));
+    let motif = Canvas::with_view_box(view_box, YAxis::Down).add_circle(
+        10.0,
+        point!(x: 0.0, y: 0.0),
+        config.clone(),
+    );
+    let canvas = LayeredCanvas::new(Canvas::with_view_box(view_box, YAxis::Down))
+        .layer("petals")
+        .add_circle(50.0, point!(x: 0.0, y: 0.0), config)
+        .into_canvas()
+        .splice_at(&motif, point!(x: 60.0, y: 60.0), 1.0, 0.0)
+        .clip(ClipShape::Circle(90.0, point!(x: 0.0, y: 0.0)));
+    let path = "./unit_tests/canvas/no_state_attributes.svg";
+    svg::save(path, &canvas).unwrap();
+
+    let mut content = String::new();
+    let state: Vec<String> = svg::open(path, &mut content)
+        .unwrap()
+        .flat_map(|event| match event {
+            svg::parser::Event::Tag(_, _, attributes) => attributes.into_keys().collect(),
+            _ => vec![],
+        })
+        .filter(|name| name.starts_with("data-"))
+        .collect();
+    assert_eq!(state, Vec::<String>::new());
+    assert!(content.contains("linearGradient"));
+    // the bounds are still known to the canvas
+    let bounds: Rect<T> = canvas.content_bounds().unwrap();
+    assert_eq!(bounds.max(), point!(x: 70.5, y: 70.5).into());
+}
+
+#[test]
+fn test_definitions_share_one_defs_element() {
+    use crate::color::Color;
+    use crate::paint::{LinearGradient, Pattern};
+    use crate::scene::Scene;
+    use svg::node::element::tag::Type;
+    use svg::parser::Event;
+    let gradient = LinearGradient::new(point!(x: -10.0, y: 0.0), point!(x: 10.0, y: 0.0))
+        .stop(0.0, Color::WHITE)
+        .stop(1.0, Color::rgb(255, 0, 0));
+    let tile = Rect::new(point!(x: 0.0, y: 0.0), point!(x: 10.0, y: 10.0));
+    let dot = Scene::new().add_circle(
+        3.0,
+        point!(x: 5.0, y: 5.0),
+        Config::<T>::try_new(0.0, "none", gradient.clone()).unwrap(),
+    );
+    let pattern = Pattern::new(tile, dot);
+    let view_box = Rect::new(point!(x: -100.0, y: -100.0), point!(x: 100.0, y: 100.0));
+    let motif = Canvas::with_view_box(view_box, YAxis::Up).add_circle(
+        10.0,
+        point!(x: 0.0, y: 0.0),
+        Config::<T>::try_new(1.0, "black", gradient.clone()).unwrap(),
+    );
+    let offsets = [point!(x: 60.0, y: 0.0), point!(x: -60.0, y: 0.0)];
+    let canvas = LayeredCanvas::new(Canvas::with_view_box(view_box, YAxis::Up))
+        .layer("background")
+        .add_circle(
+            80.0,
+            point!(x: 0.0, y: 0.0),
+            Config::try_new(1.0, "none", pattern).unwrap(),
+        )
+        .layer("petals")
+        .add_circle(
+            20.0,
+            point!(x: 0.0, y: 0.0),
+            Config::try_new(1.0, "none", gradient).unwrap(),
+        )
+        .into_canvas()
+        .splice_many(
+            &motif,
+            offsets
+                .iter()
+                .map(|offset| AffineTransform::translate(offset.x(), offset.y()))
+                .collect(),
+        )
+        .clip(ClipShape::Circle(90.0, point!(x: 0.0, y: 0.0)))
+        .set_title("defs");
+    let content = canvas.to_string();
+
+    // every `<defs>` with the depth of its parent, the root being at depth 1
+    let mut depth = 0;
+    let mut defs = vec![];
+    for event in svg::read(&content).unwrap() {
+        if let Event::Tag(name, kind, _) = event {
+            match kind {
+                Type::Start => depth += 1,
+                Type::End => depth -= 1,
+                Type::Empty => (),
+            }
+            if name == "defs" && kind != Type::End {
+                defs.push(depth - (kind == Type::Start) as i32);
+            }
+        }
+    }
+    assert_eq!(defs, vec![1]);
+    assert!(content.find("<title").unwrap() < content.find("<defs").unwrap());
+    assert_eq!(content.matches("<linearGradient").count(), 1);
+    assert_eq!(content.matches("<pattern").count(), 1);
+    assert_eq!(content.matches("<symbol").count(), 1);
+    assert_eq!(content.matches("<clipPath").count(), 1);
+}
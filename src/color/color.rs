@@ -0,0 +1,367 @@
+//! Colours in sRGB with an alpha channel, parsed from and written as css colours.
+use std::fmt;
+use std::str::FromStr;
+
+use super::named_colors::named_color;
+use super::traditional_colors::traditional_color;
+
+/// An sRGB colour, every channel is in `[0, 1]` and alpha isn't premultiplied.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Color {
+    pub red: f64,
+    pub green: f64,
+    pub blue: f64,
+    pub alpha: f64,
+}
+
+/// The colour space in which colours are interpolated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Channel by channel in sRGB, as svg gradients do.
+    #[default]
+    Srgb,
+    /// Hue, saturation and lightness, going the shorter way around the hue circle.
+    Hsl,
+    /// The perceptual OKLab space, steps look evenly spaced.
+    Oklab,
+}
+
+/// Why a string isn't a colour.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// A `#` followed by something else than 3, 4, 6 or 8 hex digits.
+    InvalidHex(String),
+    /// An `rgb()`, `rgba()`, `hsl()` or `hsla()` with missing or out of range arguments.
+    InvalidFunction(String),
+    /// Neither a hex colour, a colour function nor a known colour name.
+    UnknownColor(String),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseColorError::InvalidHex(input) => write!(f, "invalid hex colour `{}`", input),
+            ParseColorError::InvalidFunction(input) => {
+                write!(f, "invalid colour function `{}`", input)
+            }
+            ParseColorError::UnknownColor(input) => write!(f, "unknown colour `{}`", input),
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+impl Color {
+    pub const BLACK: Color = Color::new(0.0, 0.0, 0.0, 1.0);
+    pub const WHITE: Color = Color::new(1.0, 1.0, 1.0, 1.0);
+    pub const TRANSPARENT: Color = Color::new(0.0, 0.0, 0.0, 0.0);
+
+    /// A colour from channels in `[0, 1]`.
+    pub const fn new(red: f64, green: f64, blue: f64, alpha: f64) -> Self {
+        Color {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// An opaque colour from 8 bit channels, as in `#rrggbb`.
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Color::new(
+            red as f64 / 255.0,
+            green as f64 / 255.0,
+            blue as f64 / 255.0,
+            1.0,
+        )
+    }
+
+    /// An opaque colour from its hue in degrees, saturation and lightness in `[0, 1]`.
+    pub fn hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let sector = hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (red, green, blue) = match sector as usize {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = lightness - chroma / 2.0;
+        Color::new(red + m, green + m, blue + m, 1.0)
+    }
+
+    /// An opaque colour from its OKLab lightness and `a`, `b` coordinates.
+    /// Colours outside of sRGB are clipped to it.
+    pub fn oklab(lightness: f64, a: f64, b: f64) -> Self {
+        let l = (lightness + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let m = (lightness - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let s = (lightness - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+        let gamma = |linear: f64| match linear <= 0.0031308 {
+            true => 12.92 * linear,
+            false => 1.055 * linear.powf(1.0 / 2.4) - 0.055,
+        };
+        let channel = |linear: f64| gamma(linear).clamp(0.0, 1.0);
+        Color::new(
+            channel(4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s),
+            channel(-1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s),
+            channel(-0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s),
+            1.0,
+        )
+    }
+
+    /// The same colour with another alpha.
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Red, green, blue and alpha rounded to 8 bits.
+    pub fn to_rgba8(&self) -> [u8; 4] {
+        let byte = |channel: f64| (channel.clamp(0.0, 1.0) * 255.0).round() as u8;
+        [
+            byte(self.red),
+            byte(self.green),
+            byte(self.blue),
+            byte(self.alpha),
+        ]
+    }
+
+    /// The colour as `#rrggbb`, ignoring alpha.
+    pub fn to_hex(&self) -> String {
+        let [red, green, blue, _] = self.to_rgba8();
+        format!("#{:02x}{:02x}{:02x}", red, green, blue)
+    }
+
+    /// Hue in degrees in `[0, 360)`, saturation and lightness in `[0, 1]`.
+    pub fn to_hsl(&self) -> (f64, f64, f64) {
+        let (red, green, blue) = (self.red, self.green, self.blue);
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let lightness = (max + min) / 2.0;
+        let chroma = max - min;
+        if chroma <= f64::EPSILON {
+            return (0.0, 0.0, lightness);
+        }
+        let saturation = chroma / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = match max {
+            max if max == red => ((green - blue) / chroma).rem_euclid(6.0),
+            max if max == green => (blue - red) / chroma + 2.0,
+            _ => (red - green) / chroma + 4.0,
+        };
+        (60.0 * hue, saturation, lightness)
+    }
+
+    /// OKLab lightness and `a`, `b` coordinates.
+    pub fn to_oklab(&self) -> (f64, f64, f64) {
+        let linear = |channel: f64| match channel <= 0.04045 {
+            true => channel / 12.92,
+            false => ((channel + 0.055) / 1.055).powf(2.4),
+        };
+        let (red, green, blue) = (linear(self.red), linear(self.green), linear(self.blue));
+        let l = (0.4122214708 * red + 0.5363325363 * green + 0.0514459929 * blue).cbrt();
+        let m = (0.2119034982 * red + 0.6806995451 * green + 0.1073969566 * blue).cbrt();
+        let s = (0.0883024619 * red + 0.2817188376 * green + 0.6299787005 * blue).cbrt();
+        (
+            0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        )
+    }
+
+    /// The colour at `t` between `self` (at `0`) and `other` (at `1`), interpolated in `space`.
+    /// Alpha is always interpolated linearly.
+    pub fn interpolate(&self, other: &Color, t: f64, space: ColorSpace) -> Color {
+        let lerp = |from: f64, to: f64| from + (to - from) * t;
+        let alpha = lerp(self.alpha, other.alpha);
+        match space {
+            ColorSpace::Srgb => Color::new(
+                lerp(self.red, other.red),
+                lerp(self.green, other.green),
+                lerp(self.blue, other.blue),
+                alpha,
+            ),
+            ColorSpace::Hsl => {
+                let (mut from_hue, from_saturation, from_lightness) = self.to_hsl();
+                let (mut to_hue, to_saturation, to_lightness) = other.to_hsl();
+                // Greys have no hue of their own
+                if from_saturation == 0.0 {
+                    from_hue = to_hue;
+                }
+                if to_saturation == 0.0 {
+                    to_hue = from_hue;
+                }
+                let turn = (to_hue - from_hue + 180.0).rem_euclid(360.0) - 180.0;
+                Color::hsl(
+                    from_hue + turn * t,
+                    lerp(from_saturation, to_saturation),
+                    lerp(from_lightness, to_lightness),
+                )
+                .with_alpha(alpha)
+            }
+            ColorSpace::Oklab => {
+                let (from_lightness, from_a, from_b) = self.to_oklab();
+                let (to_lightness, to_a, to_b) = other.to_oklab();
+                Color::oklab(
+                    lerp(from_lightness, to_lightness),
+                    lerp(from_a, to_a),
+                    lerp(from_b, to_b),
+                )
+                .with_alpha(alpha)
+            }
+        }
+    }
+
+    /// `steps` colours evenly spaced from `self` to `other`, both included,
+    /// e.g. to shade successive rings of leaves.
+    pub fn ramp(&self, other: &Color, steps: usize, space: ColorSpace) -> Vec<Color> {
+        match steps {
+            0 => vec![],
+            1 => vec![*self],
+            _ => (0..steps)
+                .map(|i| self.interpolate(other, i as f64 / (steps - 1) as f64, space))
+                .collect(),
+        }
+    }
+}
+
+/// Parse the arguments of a css colour function, percentages are divided by 100.
+fn arguments(arguments: &str) -> Option<Vec<(f64, bool)>> {
+    arguments
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|argument| !argument.is_empty())
+        .map(|argument| match argument.strip_suffix('%') {
+            Some(percent) => percent.parse::<f64>().ok().map(|p| (p / 100.0, true)),
+            None => argument
+                .strip_suffix("deg")
+                .unwrap_or(argument)
+                .parse::<f64>()
+                .ok()
+                .map(|value| (value, false)),
+        })
+        .map(|argument| argument.filter(|(value, _)| value.is_finite()))
+        .collect()
+}
+
+/// An alpha given as a number or a percentage.
+fn alpha(argument: Option<&(f64, bool)>) -> Option<f64> {
+    match argument {
+        None => Some(1.0),
+        Some(&(alpha, _)) => Some(alpha).filter(|alpha| (0.0..=1.0).contains(alpha)),
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let double = |c: char| format!("{}{}", c, c);
+    let hex: String = match hex.len() {
+        3 | 4 => hex.chars().map(double).collect(),
+        6 | 8 => hex.to_string(),
+        _ => return None,
+    };
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let color = Color::rgb(channel(0)?, channel(2)?, channel(4)?);
+    match hex.len() {
+        8 => Some(color.with_alpha(channel(6)? as f64 / 255.0)),
+        _ => Some(color),
+    }
+}
+
+fn parse_rgb(rgb: &str) -> Option<Color> {
+    let rgb = arguments(rgb)?;
+    if !(3..=4).contains(&rgb.len()) {
+        return None;
+    }
+    let channel = |&(value, percent): &(f64, bool)| {
+        let value = if percent { value } else { value / 255.0 };
+        Some(value).filter(|value| (0.0..=1.0).contains(value))
+    };
+    Some(Color::new(
+        channel(&rgb[0])?,
+        channel(&rgb[1])?,
+        channel(&rgb[2])?,
+        alpha(rgb.get(3))?,
+    ))
+}
+
+fn parse_hsl(hsl: &str) -> Option<Color> {
+    let hsl = arguments(hsl)?;
+    if !(3..=4).contains(&hsl.len()) || hsl[0].1 {
+        return None;
+    }
+    // Saturation and lightness are percentages, bare numbers are read as such too
+    let fraction = |&(value, percent): &(f64, bool)| {
+        let value = if percent { value } else { value / 100.0 };
+        Some(value).filter(|value| (0.0..=1.0).contains(value))
+    };
+    Some(
+        Color::hsl(hsl[0].0, fraction(&hsl[1])?, fraction(&hsl[2])?).with_alpha(alpha(hsl.get(3))?),
+    )
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parse a css colour: `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`, `rgb()`, `rgba()`,
+    /// `hsl()`, `hsla()`, `transparent`, one of the css named colours or one of the
+    /// [traditional colours](super::traditional_colors), ignoring case.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let color = input.trim().to_ascii_lowercase();
+        if let Some(hex) = color.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(|| ParseColorError::InvalidHex(input.to_string()));
+        }
+        let function = |name: &str| {
+            color
+                .strip_prefix(name)
+                .and_then(|rest| rest.trim_start().strip_prefix('('))
+                .and_then(|rest| rest.strip_suffix(')'))
+        };
+        let parsed = match (
+            function("rgba").or_else(|| function("rgb")),
+            function("hsla").or_else(|| function("hsl")),
+        ) {
+            (Some(rgb), _) => parse_rgb(rgb),
+            (_, Some(hsl)) => parse_hsl(hsl),
+            _ if color == "transparent" => return Ok(Color::TRANSPARENT),
+            _ => {
+                return named_color(&color)
+                    .map(|(red, green, blue)| Color::rgb(red, green, blue))
+                    .or_else(|| traditional_color(&color))
+                    .ok_or_else(|| ParseColorError::UnknownColor(input.to_string()))
+            }
+        };
+        parsed.ok_or_else(|| ParseColorError::InvalidFunction(input.to_string()))
+    }
+}
+
+impl TryFrom<&str> for Color {
+    type Error = ParseColorError;
+
+    fn try_from(color: &str) -> Result<Self, Self::Error> {
+        color.parse()
+    }
+}
+
+/// Opaque colours are written as `#rrggbb`, translucent ones as `rgba()`.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [red, green, blue, alpha] = self.to_rgba8();
+        match alpha {
+            255 => write!(f, "{}", self.to_hex()),
+            _ => write!(
+                f,
+                "rgba({}, {}, {}, {})",
+                red,
+                green,
+                blue,
+                (self.alpha.clamp(0.0, 1.0) * 1000.0).round() / 1000.0
+            ),
+        }
+    }
+}
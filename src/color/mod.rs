@@ -0,0 +1,12 @@
+#[allow(clippy::module_inception)]
+pub mod color;
+pub use color::{Color, ColorSpace, ParseColorError};
+pub(crate) mod named_colors;
+pub mod palette;
+pub use palette::Palette;
+pub mod theme;
+pub mod traditional_colors;
+pub use theme::{Theme, Themed};
+
+#[cfg(test)]
+pub mod test_color;
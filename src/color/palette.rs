@@ -0,0 +1,129 @@
+//! Palettes: the colours and stroke styles of the parts of multi-part art forms.
+use geo_types::CoordFloat;
+
+use super::traditional_colors::{colors_of, indian, Culture};
+use super::{Color, ColorSpace, Theme};
+use crate::art_forms::base_shapes::Config;
+use crate::error::{or_panic, Error, Result};
+use crate::paint::Paint;
+
+/// An ordered set of named parts, each with its own fill and stroke.
+/// The parts are handed to art forms drawn in several parts, like [`add_sri`](crate::art_forms::yantra::Yantra::add_sri),
+/// in order with [`Palette::configs`] or one by one by name with [`Palette::config`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette<T: CoordFloat> {
+    pub name: String,
+    pub parts: Vec<(String, Config<T>)>,
+}
+
+impl<T: CoordFloat> Palette<T> {
+    pub fn new(name: &str) -> Self {
+        Palette {
+            name: name.to_string(),
+            parts: vec![],
+        }
+    }
+
+    /// Add the part `name` at the end, or replace the config of the existing part `name`.
+    pub fn part(mut self, name: &str, config: Config<T>) -> Self {
+        match self.parts.iter_mut().find(|(part, _)| part == name) {
+            Some((_, existing)) => *existing = config,
+            None => self.parts.push((name.to_string(), config)),
+        }
+        self
+    }
+
+    /// Add the part `name` filled with `color`, without stroke.
+    pub fn fill(self, name: &str, color: Color) -> Self {
+        self.part(name, Config::from_paints(T::one(), Paint::None, color))
+    }
+
+    /// The config of the part `name`.
+    pub fn get(&self, name: &str) -> Option<&Config<T>> {
+        self.parts
+            .iter()
+            .find(|(part, _)| part == name)
+            .map(|(_, config)| config)
+    }
+
+    /// The config of the part `name`, panicking if the palette has no such part.
+    pub fn config(&self, name: &str) -> Config<T> {
+        or_panic(self.try_config(name))
+    }
+
+    /// The config of the part `name`, failing if the palette has no such part.
+    pub fn try_config(&self, name: &str) -> Result<Config<T>> {
+        self.get(name)
+            .cloned()
+            .ok_or_else(|| Error::MissingPart(name.to_string()))
+    }
+
+    /// The configs of the first `N` parts, in order, panicking if the palette has less than `N` parts.
+    pub fn configs<const N: usize>(&self) -> [Config<T>; N] {
+        or_panic(self.try_configs())
+    }
+
+    /// The configs of the first `N` parts, in order, failing if the palette has less than `N` parts.
+    pub fn try_configs<const N: usize>(&self) -> Result<[Config<T>; N]> {
+        match self.parts.len() >= N {
+            true => Ok(std::array::from_fn(|i| self.parts[i].1.clone())),
+            false => Err(Error::TooFewParts {
+                needed: N,
+                found: self.parts.len(),
+            }),
+        }
+    }
+
+    /// The same parts recoloured by `theme`.
+    pub fn with_theme(&self, theme: Theme) -> Self {
+        Palette {
+            name: self.name.clone(),
+            parts: self
+                .parts
+                .iter()
+                .map(|(name, config)| (name.clone(), theme.config(config)))
+                .collect(),
+        }
+    }
+
+    /// `n` parts named `0` to `n - 1` filled with colours evenly spaced from `from` to `to`.
+    pub fn ramp(name: &str, from: Color, to: Color, n: usize, space: ColorSpace) -> Self {
+        from.ramp(&to, n, space)
+            .into_iter()
+            .enumerate()
+            .fold(Palette::new(name), |palette, (i, color)| {
+                palette.fill(&i.to_string(), color)
+            })
+    }
+
+    /// The traditional colours of `culture`, one part per colour named after it.
+    pub fn traditional(culture: Culture) -> Self {
+        colors_of(culture)
+            .into_iter()
+            .fold(Palette::new(culture.name()), |palette, (name, color)| {
+                palette.fill(name, color)
+            })
+    }
+
+    /// The traditional colours of the Sri Yantra.
+    /// The first nine parts are the triangles drawn by `add_sri`, from the outermost in,
+    /// followed by the `background` circle, the `outer petals` (16), the `inner petals` (8)
+    /// and the `bindu` at the center.
+    pub fn sri_yantra() -> Self {
+        let background = Color::rgb(0xe8, 0xfb, 0x62);
+        Palette::new("sri yantra")
+            .fill("first outer", indian::GLOW_WORM)
+            .fill("first inner", background)
+            .fill("second outer", indian::JAPAKUSUMA)
+            .fill("second inner", background)
+            .fill("third outer", Color::rgb(0x7c, 0x96, 0xf7))
+            .fill("third inner", background)
+            .fill("fourth outer", indian::DADINI)
+            .fill("fourth inner", background)
+            .fill("fifth outer", Color::WHITE)
+            .fill("background", background)
+            .fill("outer petals", Color::rgb(0xff, 0xc0, 0xcb))
+            .fill("inner petals", indian::BANDHUKA)
+            .fill("bindu", indian::KUMKUM)
+    }
+}
@@ -0,0 +1,303 @@
+use super::{Color, ColorSpace, ParseColorError};
+use crate::art_forms::base_shapes::Config;
+use crate::art_forms::leaves::{LeafStyle, Leaves};
+use crate::error::Error;
+use crate::paint::Paint;
+use crate::Canvas;
+use geo::point;
+
+type T = f64;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+#[test]
+fn test_parse_colors() {
+    let pink = Color::rgb(0xe8, 0xfb, 0x62);
+    assert_eq!("#E8FB62".parse(), Ok(pink));
+    assert_eq!("#e8fb62ff".parse(), Ok(pink));
+    assert_eq!("rgb(232, 251, 98)".parse(), Ok(pink));
+    assert_eq!("rgb(232 251 98 / 100%)".parse(), Ok(pink));
+    assert_eq!("#f00".parse(), Ok(Color::rgb(255, 0, 0)));
+    assert_eq!("Red".parse(), Ok(Color::rgb(255, 0, 0)));
+    assert_eq!("hsl(120, 100%, 50%)".parse(), Ok(Color::rgb(0, 255, 0)));
+    assert_eq!(
+        "hsla(240deg, 100%, 50%, 0.5)".parse(),
+        Ok(Color::rgb(0, 0, 255).with_alpha(0.5))
+    );
+    assert_eq!("transparent".parse(), Ok(Color::TRANSPARENT));
+
+    assert_eq!(
+        "#E8FB6".parse::<Color>(),
+        Err(ParseColorError::InvalidHex("#E8FB6".to_string()))
+    );
+    assert_eq!(
+        "#E8FBGG".parse::<Color>(),
+        Err(ParseColorError::InvalidHex("#E8FBGG".to_string()))
+    );
+    assert!(matches!(
+        "rgb(300, 0, 0)".parse::<Color>(),
+        Err(ParseColorError::InvalidFunction(_))
+    ));
+    assert!(matches!(
+        "rgb(1, 2)".parse::<Color>(),
+        Err(ParseColorError::InvalidFunction(_))
+    ));
+    assert!(matches!(
+        "hsl(10, 50%, 120%)".parse::<Color>(),
+        Err(ParseColorError::InvalidFunction(_))
+    ));
+    assert!(matches!(
+        "pinkish".parse::<Color>(),
+        Err(ParseColorError::UnknownColor(_))
+    ));
+    assert_eq!(
+        "#E8FB6".parse::<Color>().unwrap_err().to_string(),
+        "invalid hex colour `#E8FB6`"
+    );
+}
+
+#[test]
+fn test_write_colors() {
+    assert_eq!(Color::rgb(0xe8, 0xfb, 0x62).to_string(), "#e8fb62");
+    assert_eq!(
+        Color::rgb(255, 0, 0).with_alpha(0.25).to_string(),
+        "rgba(255, 0, 0, 0.25)"
+    );
+    let paint: Paint<T> = "#E8FB62".try_into().unwrap();
+    assert_eq!(paint.svg_value(), "#e8fb62");
+    assert!(paint == "#e8fb62");
+    assert_eq!(Paint::<T>::try_from("none").unwrap().svg_value(), "none");
+    assert!("#E8FB6".parse::<Paint<T>>().is_err());
+}
+
+#[test]
+fn test_invalid_colors_are_rejected_by_config() {
+    let error = Config::<T>::try_new(1.0, "black", "#E8FB6").unwrap_err();
+    assert_eq!(
+        error,
+        Error::Color(ParseColorError::InvalidHex("#E8FB6".to_string()))
+    );
+    assert_eq!(error.to_string(), "invalid hex colour `#E8FB6`");
+}
+
+#[test]
+fn test_color_spaces() {
+    let color = Color::rgb(0x90, 0x00, 0x07);
+    let (hue, saturation, lightness) = color.to_hsl();
+    let back = Color::hsl(hue, saturation, lightness);
+    assert_eq!(back.to_rgba8(), color.to_rgba8());
+    let (l, a, b) = color.to_oklab();
+    assert_eq!(Color::oklab(l, a, b).to_rgba8(), color.to_rgba8());
+
+    let (l, a, b) = Color::WHITE.to_oklab();
+    assert!(close(l, 1.0) && a.abs() < 1e-4 && b.abs() < 1e-4);
+    let (hue, saturation, lightness) = Color::rgb(0, 0, 255).to_hsl();
+    assert!(close(hue, 240.0) && close(saturation, 1.0) && close(lightness, 0.5));
+}
+
+#[test]
+fn test_interpolation() {
+    let red = Color::rgb(255, 0, 0);
+    let blue = Color::rgb(0, 0, 255);
+    assert_eq!(
+        red.interpolate(&blue, 0.5, ColorSpace::Srgb).to_rgba8(),
+        [128, 0, 128, 255]
+    );
+    // the short way from red to blue around the hue circle goes through magenta
+    assert_eq!(
+        red.interpolate(&blue, 0.5, ColorSpace::Hsl).to_rgba8(),
+        [255, 0, 255, 255]
+    );
+    // OKLab keeps the midpoint between black and white perceptually grey
+    let grey = Color::BLACK.interpolate(&Color::WHITE, 0.5, ColorSpace::Oklab);
+    assert!(close(grey.to_oklab().0, 0.5));
+    assert!(grey.red > 0.35 && grey.red < 0.4);
+
+    let ramp = red.ramp(&blue, 5, ColorSpace::Oklab);
+    assert_eq!(ramp.len(), 5);
+    assert_eq!(ramp[0].to_rgba8(), red.to_rgba8());
+    assert_eq!(ramp[4].to_rgba8(), blue.to_rgba8());
+    let lightness: Vec<f64> = ramp.iter().map(|color| color.to_oklab().0).collect();
+    assert!(lightness.windows(2).all(|pair| pair[1] < pair[0]));
+}
+
+#[test]
+fn test_shading_leaf_rings() {
+    let view_size = 150.0;
+    let mut canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let center = point!(x: 0.0, y: 0.0);
+    let shades =
+        Color::rgb(0x90, 0x00, 0x07).ramp(&Color::rgb(0xfd, 0x9d, 0x97), 4, ColorSpace::Oklab);
+    for (ring, shade) in shades.iter().enumerate() {
+        let radius = 120.0 - 25.0 * ring as T;
+        let style = LeafStyle::<T>::new_reniform(radius / 4.0, 1.1, 12.0, 13.0);
+        canvas = canvas.add_circular_leaves(
+            radius,
+            center,
+            0.0,
+            16,
+            1.0,
+            style,
+            Config::try_new(1.0, "#900007", *shade).unwrap(),
+        );
+    }
+    let content = canvas.to_string();
+    for shade in shades {
+        assert_eq!(content.matches(&format!(r#"fill="{}""#, shade)).count(), 16);
+    }
+    svg::save("./unit_tests/color/leaf_ring_shades.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_palettes() {
+    use super::Palette;
+    let sri = Palette::<T>::sri_yantra();
+    let configs = sri.configs::<9>();
+    assert_eq!(configs[2].fill(), "#fd5e36");
+    assert_eq!(configs[8].fill(), "white");
+    assert_eq!(sri.config("bindu").fill(), "#f33625");
+    assert!(sri.get("lotus").is_none());
+
+    let sri = sri.part("bindu", Config::try_new(1.0, "black", "gold").unwrap());
+    assert_eq!(sri.parts.len(), 13);
+    assert_eq!(sri.config("bindu").stroke(), "black");
+
+    let rings = Palette::<T>::ramp("rings", Color::BLACK, Color::WHITE, 3, ColorSpace::Srgb);
+    let [dark, grey, light] = rings.configs();
+    assert_eq!(dark.fill(), "black");
+    assert_eq!(grey.fill().svg_value(), "#808080");
+    assert_eq!(light, rings.config("2"));
+}
+
+#[test]
+fn test_palette_too_short() {
+    use super::Palette;
+    let rings = Palette::<T>::ramp("rings", Color::BLACK, Color::WHITE, 2, ColorSpace::Srgb);
+    assert_eq!(
+        rings.try_configs::<9>().err(),
+        Some(Error::TooFewParts {
+            needed: 9,
+            found: 2
+        })
+    );
+    assert_eq!(
+        rings.try_config("lotus"),
+        Err(Error::MissingPart("lotus".to_string()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "the palette has 2 parts, 9 are needed")]
+fn test_palette_too_short_panics() {
+    use super::Palette;
+    Palette::<T>::ramp("rings", Color::BLACK, Color::WHITE, 2, ColorSpace::Srgb).configs::<9>();
+}
+
+#[test]
+fn test_themes() {
+    use super::{Palette, Theme, Themed};
+    use crate::art_forms::yantra::Yantra;
+    use crate::backend::Backend;
+    use crate::paint::LinearGradient;
+    use crate::scene::Scene;
+
+    let kumkum = Color::rgb(0xf3, 0x36, 0x25);
+    assert_eq!(Theme::Original.color(&kumkum), kumkum);
+    let grey = Theme::Monochrome.color(&kumkum);
+    assert!(grey.red == grey.green && grey.green == grey.blue);
+    assert!(close(grey.to_oklab().0, kumkum.to_oklab().0));
+    assert_eq!(Theme::Dark.color(&Color::WHITE).to_rgba8(), [0, 0, 0, 255]);
+    let dark = Theme::Dark.color(&Color::rgb(0xe8, 0xfb, 0x62));
+    assert!(dark.to_oklab().0 < 0.2);
+
+    let gradient = LinearGradient::new(point!(x: 0.0, y: 0.0), point!(x: 1.0, y: 0.0))
+        .stop(0.0, Color::WHITE)
+        .stop(1.0, Color::rgb(255, 0, 0));
+    let config = Config::<T>::try_new(1.0, "white", gradient).unwrap();
+    let themed = Theme::Dark.config(&config);
+    assert_eq!(themed.stroke().svg_value(), "#000000");
+    assert!(
+        matches!(&themed.fill(), Paint::LinearGradient(gradient) if gradient.stops[0].color.to_rgba8() == [0, 0, 0, 255])
+    );
+
+    // the whole canvas is switched, the palette is left as it is
+    let view_size = 150.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let palette = Palette::sri_yantra();
+    let center = point!(x: 0.0, y: 0.0);
+    let canvas = Themed::new(canvas, Theme::Monochrome)
+        .draw_circle(100.0, center, palette.config("background"))
+        .add_sri(100.0, center, palette.configs())
+        .draw_circle(1.0, center, palette.config("bindu"))
+        .into_inner();
+    let content = canvas.to_string();
+    assert!(!content.contains(&format!(r#"fill="{}""#, kumkum)));
+    assert!(content.contains(&format!(r#"fill="{}""#, grey)));
+    assert_eq!(
+        palette.with_theme(Theme::Monochrome).config("bindu").fill(),
+        Paint::Color(grey)
+    );
+    svg::save("./unit_tests/color/sri_yantra_monochrome.svg", &canvas).unwrap();
+
+    // a drawing kept as a scene switches themes after it is drawn
+    let scene = Scene::new()
+        .add_sri(100.0, center, palette.configs())
+        .draw_circle(1.0, center, palette.config("bindu"));
+    assert_eq!(scene.with_theme(Theme::Original), scene);
+    let dark = scene.with_theme(Theme::Dark);
+    let monochrome = scene.with_theme(Theme::Monochrome);
+    assert_eq!(
+        monochrome.shapes().last().unwrap().config.fill(),
+        Paint::Color(grey)
+    );
+    assert_eq!(
+        dark.shapes().last().unwrap().config.fill(),
+        Paint::Color(Theme::Dark.color(&kumkum))
+    );
+    // themes applied one after the other compose
+    assert_eq!(
+        monochrome
+            .with_theme(Theme::Dark)
+            .shapes()
+            .last()
+            .unwrap()
+            .config
+            .fill(),
+        Paint::Color(Theme::Dark.color(&grey))
+    );
+}
+
+#[test]
+fn test_traditional_colors() {
+    use super::traditional_colors::{colors_of, indian, Culture, TRADITIONAL_COLORS};
+    use super::Palette;
+    assert_eq!("Japakusuma".parse(), Ok(indian::JAPAKUSUMA));
+    assert_eq!("glow-worm".parse(), Ok(indian::GLOW_WORM));
+    assert_eq!("Hara Bhata".parse(), Ok(indian::HARA_BHATA));
+    // no traditional name is shadowed by a css name
+    for (name, _, color) in TRADITIONAL_COLORS {
+        assert_eq!(name.parse(), Ok(color), "{}", name);
+    }
+    let config = Config::<T>::try_new(1.0, "bandhuka", "kumkum").unwrap();
+    assert_eq!(config.fill().svg_value(), "#f33625");
+    assert_eq!(config.stroke().svg_value(), "#900007");
+
+    assert_eq!(colors_of(Culture::Japanese).len(), 10);
+    let indian = Palette::<T>::traditional(Culture::Indian);
+    assert_eq!(indian.name, "indian");
+    assert_eq!(indian.parts.len(), colors_of(Culture::Indian).len());
+    assert_eq!(indian.config("dadini").fill(), Paint::Color(indian::DADINI));
+    // every colour belongs to exactly one culture
+    assert_eq!(
+        colors_of(Culture::Indian).len() + colors_of(Culture::Japanese).len(),
+        TRADITIONAL_COLORS.len()
+    );
+}
@@ -0,0 +1,117 @@
+//! Themes recolour whole drawings, e.g. for a monochrome print or a dark background.
+use geo::Point;
+use geo_types::CoordFloat;
+
+use super::Color;
+use crate::art_forms::base_shapes::Config;
+use crate::backend::Backend;
+use crate::paint::{GradientStop, Paint, Pattern};
+use crate::path::Path;
+use crate::scene::Shape;
+
+/// A recolouring of every colour of a drawing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Theme {
+    /// The colours as they are given.
+    #[default]
+    Original,
+    /// Greys of the same perceived lightness, for monochrome prints.
+    Monochrome,
+    /// Light and dark swapped keeping the hues, for dark backgrounds.
+    Dark,
+}
+
+impl Theme {
+    /// The colour in this theme, lightness is taken from OKLab.
+    pub fn color(&self, color: &Color) -> Color {
+        let (lightness, a, b) = color.to_oklab();
+        match self {
+            Theme::Original => *color,
+            Theme::Monochrome => Color::oklab(lightness, 0.0, 0.0),
+            Theme::Dark => Color::oklab(1.0 - lightness, a, b),
+        }
+        .with_alpha(color.alpha)
+    }
+
+    /// The paint in this theme, gradient stops and pattern tiles are recoloured too.
+    pub fn paint<T: CoordFloat>(&self, paint: &Paint<T>) -> Paint<T> {
+        let stops = |stops: &[GradientStop<T>]| {
+            stops
+                .iter()
+                .map(|stop| GradientStop::new(stop.offset, self.color(&stop.color)))
+                .collect()
+        };
+        match paint {
+            Paint::None => Paint::None,
+            Paint::Color(color) => Paint::Color(self.color(color)),
+            Paint::LinearGradient(gradient) => {
+                let mut gradient = gradient.clone();
+                gradient.stops = stops(&gradient.stops);
+                Paint::LinearGradient(gradient)
+            }
+            Paint::RadialGradient(gradient) => {
+                let mut gradient = gradient.clone();
+                gradient.stops = stops(&gradient.stops);
+                Paint::RadialGradient(gradient)
+            }
+            Paint::Pattern(pattern) => Paint::Pattern(Pattern::new(
+                pattern.tile,
+                pattern.content.with_theme(*self),
+            )),
+        }
+    }
+
+    /// The config with its fill and stroke in this theme.
+    pub fn config<T: CoordFloat>(&self, config: &Config<T>) -> Config<T> {
+        config
+            .clone()
+            .with_fill_paint(self.paint(&config.fill()))
+            .with_stroke_paint(self.paint(&config.stroke()))
+    }
+}
+
+/// A backend drawing everything on `backend` in the colours of `theme`,
+/// so switching the theme of a whole canvas is a matter of drawing it through this.
+/// The theme has to be chosen before drawing: content already on `backend` keeps its colours.
+/// A drawing kept as a [`Scene`](crate::scene::Scene) can switch themes after it is drawn, see [`Scene::with_theme`](crate::scene::Scene::with_theme).
+#[derive(Clone, Debug)]
+pub struct Themed<B> {
+    backend: B,
+    theme: Theme,
+}
+
+impl<B> Themed<B> {
+    pub fn new(backend: B, theme: Theme) -> Self {
+        Themed { backend, theme }
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.theme
+    }
+
+    /// The backend with everything drawn so far.
+    pub fn into_inner(self) -> B {
+        self.backend
+    }
+}
+
+impl<T: CoordFloat, B: Backend<T>> Backend<T> for Themed<B> {
+    fn draw_circle(mut self, radius: T, center: Point<T>, config: Config<T>) -> Self {
+        let config = self.theme.config(&config);
+        self.backend = self.backend.draw_circle(radius, center, config);
+        self
+    }
+
+    fn draw_path(mut self, path: Path<T>, config: Config<T>) -> Self {
+        let config = self.theme.config(&config);
+        self.backend = self.backend.draw_path(path, config);
+        self
+    }
+
+    fn draw_shape(mut self, shape: &Shape<T>) -> Self {
+        let mut shape = shape.clone();
+        shape.config = self.theme.config(&shape.config);
+        self.backend = self.backend.draw_shape(&shape);
+        self
+    }
+}
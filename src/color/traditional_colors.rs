@@ -0,0 +1,150 @@
+//! Traditional pigment and flower colours, by culture.
+//! The values are common renderings of the pigments and flowers, not measurements.
+//! Every colour can also be given by name wherever a colour is parsed, e.g.
+//! `Config::try_new(1.0, "none", "japakusuma")`, names ignore case, spaces and hyphens.
+use super::Color;
+
+/// Colours of Indian pigments and of the flowers named in yantra and puja traditions.
+pub mod indian {
+    use super::Color;
+
+    /// Red of the Japakusuma (hibiscus) flower, offered to the Devi.
+    pub const JAPAKUSUMA: Color = Color::rgb(0xfd, 0x5e, 0x36);
+    /// Deep red of the Bandhuka flower.
+    pub const BANDHUKA: Color = Color::rgb(0x90, 0x00, 0x07);
+    /// Pink of the dadini (pomegranate) flower.
+    pub const DADINI: Color = Color::rgb(0xfd, 0x9d, 0x97);
+    /// Red of kumkum, the powder of turmeric and lime.
+    pub const KUMKUM: Color = Color::rgb(0xf3, 0x36, 0x25);
+    /// Brownish grey of the glow worm.
+    pub const GLOW_WORM: Color = Color::rgb(0x7b, 0x70, 0x64);
+    /// Orange red of sindoor, vermilion.
+    pub const SINDOOR: Color = Color::rgb(0xe3, 0x42, 0x34);
+    /// Yellow of haldi, turmeric.
+    pub const HALDI: Color = Color::rgb(0xe3, 0xa0, 0x18);
+    /// Orange yellow of kesar, saffron.
+    pub const KESAR: Color = Color::rgb(0xf4, 0xc4, 0x30);
+    /// Warm yellow of peori, Indian yellow.
+    pub const PEORI: Color = Color::rgb(0xe3, 0xa8, 0x57);
+    /// Earthy red of geru, red ochre.
+    pub const GERU: Color = Color::rgb(0xa5, 0x49, 0x2a);
+    /// Blue of neel, indigo.
+    pub const NEEL: Color = Color::rgb(0x1f, 0x3b, 0x73);
+    /// Blue of lajvard, lapis lazuli.
+    pub const LAJVARD: Color = Color::rgb(0x26, 0x61, 0x9c);
+    /// Green of hara bhata, malachite.
+    pub const HARA_BHATA: Color = Color::rgb(0x2e, 0x8b, 0x57);
+    /// Black of kajal, lamp black.
+    pub const KAJAL: Color = Color::rgb(0x1b, 0x1b, 0x1b);
+    /// White of chuna, lime.
+    pub const CHUNA: Color = Color::rgb(0xf5, 0xf3, 0xea);
+    /// Orange of the palash flower, the flame of the forest.
+    pub const PALASH: Color = Color::rgb(0xe2, 0x58, 0x22);
+    /// Pink of the kamala, the lotus.
+    pub const KAMALA: Color = Color::rgb(0xf6, 0xa6, 0xb2);
+    /// Pale yellow of the champa flower.
+    pub const CHAMPA: Color = Color::rgb(0xf7, 0xd5, 0x7a);
+}
+
+/// Traditional Japanese colours.
+pub mod japanese {
+    use super::Color;
+
+    /// Deep blue of ai, indigo.
+    pub const AI: Color = Color::rgb(0x0d, 0x56, 0x61);
+    /// Crimson of beni, safflower red.
+    pub const BENI: Color = Color::rgb(0xcb, 0x1b, 0x45);
+    /// Vermilion of shu, cinnabar.
+    pub const SHU: Color = Color::rgb(0xe8, 0x39, 0x29);
+    /// Pale pink of sakura, cherry blossom.
+    pub const SAKURA: Color = Color::rgb(0xfe, 0xdf, 0xe1);
+    /// Golden yellow of the yamabuki flower.
+    pub const YAMABUKI: Color = Color::rgb(0xff, 0xb1, 0x1b);
+    /// Olive of uguisu, the bush warbler.
+    pub const UGUISU: Color = Color::rgb(0x6c, 0x6a, 0x2d);
+    /// Light blue of wasurenagusa, the forget-me-not.
+    pub const WASURENAGUSA: Color = Color::rgb(0x7d, 0xb9, 0xde);
+    /// Greenish blue of asagi.
+    pub const ASAGI: Color = Color::rgb(0x33, 0xa6, 0xb8);
+    /// Orange of kuchiba, fallen leaves.
+    pub const KUCHIBA: Color = Color::rgb(0xe2, 0x94, 0x3b);
+    /// Black of sumi, ink.
+    pub const SUMI: Color = Color::rgb(0x1c, 0x1c, 0x1c);
+}
+
+/// The cultures with traditional colours.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Culture {
+    /// Colours of the [`indian`] module.
+    Indian,
+    /// Colours of the [`japanese`] module.
+    Japanese,
+}
+
+impl Culture {
+    /// Lowercase name of the culture, like `indian`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Culture::Indian => "indian",
+            Culture::Japanese => "japanese",
+        }
+    }
+}
+
+/// Name, culture and value of every traditional colour.
+pub const TRADITIONAL_COLORS: [(&str, Culture, Color); 28] = [
+    ("japakusuma", Culture::Indian, indian::JAPAKUSUMA),
+    ("bandhuka", Culture::Indian, indian::BANDHUKA),
+    ("dadini", Culture::Indian, indian::DADINI),
+    ("kumkum", Culture::Indian, indian::KUMKUM),
+    ("glow worm", Culture::Indian, indian::GLOW_WORM),
+    ("sindoor", Culture::Indian, indian::SINDOOR),
+    ("haldi", Culture::Indian, indian::HALDI),
+    ("kesar", Culture::Indian, indian::KESAR),
+    ("peori", Culture::Indian, indian::PEORI),
+    ("geru", Culture::Indian, indian::GERU),
+    ("neel", Culture::Indian, indian::NEEL),
+    ("lajvard", Culture::Indian, indian::LAJVARD),
+    ("hara bhata", Culture::Indian, indian::HARA_BHATA),
+    ("kajal", Culture::Indian, indian::KAJAL),
+    ("chuna", Culture::Indian, indian::CHUNA),
+    ("palash", Culture::Indian, indian::PALASH),
+    ("kamala", Culture::Indian, indian::KAMALA),
+    ("champa", Culture::Indian, indian::CHAMPA),
+    ("ai", Culture::Japanese, japanese::AI),
+    ("beni", Culture::Japanese, japanese::BENI),
+    ("shu", Culture::Japanese, japanese::SHU),
+    ("sakura", Culture::Japanese, japanese::SAKURA),
+    ("yamabuki", Culture::Japanese, japanese::YAMABUKI),
+    ("uguisu", Culture::Japanese, japanese::UGUISU),
+    ("wasurenagusa", Culture::Japanese, japanese::WASURENAGUSA),
+    ("asagi", Culture::Japanese, japanese::ASAGI),
+    ("kuchiba", Culture::Japanese, japanese::KUCHIBA),
+    ("sumi", Culture::Japanese, japanese::SUMI),
+];
+
+/// Lowercase without spaces, hyphens and underscores.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// The traditional colour `name`, ignoring case, spaces and hyphens.
+pub(crate) fn traditional_color(name: &str) -> Option<Color> {
+    let name = normalize(name);
+    TRADITIONAL_COLORS
+        .iter()
+        .find(|(other, ..)| normalize(other) == name)
+        .map(|(.., color)| *color)
+}
+
+/// Names and values of the traditional colours of `culture`, in the order of [`TRADITIONAL_COLORS`].
+pub fn colors_of(culture: Culture) -> Vec<(&'static str, Color)> {
+    TRADITIONAL_COLORS
+        .iter()
+        .filter(|(_, other, _)| *other == culture)
+        .map(|(name, _, color)| (*name, *color))
+        .collect()
+}
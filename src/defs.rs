@@ -0,0 +1,13 @@
+//! Ids of definitions, like gradients, patterns, clip paths and symbols.
+//! They are shared by the paints and the canvas, which define each of them once.
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+
+/// A deterministic id for a definition, derived from its content.
+/// Identical definitions get identical ids.
+pub(crate) fn definition_id(prefix: &str, definition: &impl Display) -> String {
+    let mut hasher = DefaultHasher::new();
+    definition.to_string().hash(&mut hasher);
+    format!("{}-{:x}", prefix, hasher.finish())
+}
@@ -0,0 +1,137 @@
+//! Errors of the fallible `try_` variants of the art forms and canvas operations.
+//! The other variants panic with the message of the same error.
+use std::fmt;
+
+use crate::color::ParseColorError;
+
+/// The constraint an art form or a colour didn't satisfy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Two lines that were expected to cross are parallel or collinear.
+    NoIntersection,
+    /// A polygon was asked for with less than three vertices.
+    TooFewVertices(usize),
+    /// A star polygon `{p/q}` was asked for with `q = 0`.
+    ZeroStep,
+    /// An isotoxal star `{p/q}` was asked for with `p` and `q` not coprime.
+    NotCoprime { p: usize, q: usize },
+    /// An isotoxal star `{p/q}` is only concave if `q mod p` is neither `0`, `1` nor `p - 1`.
+    NotConcave { p: usize, q: usize },
+    /// A rose or a spirograph was asked for with a zero term in its ratio `n / d`.
+    ZeroRatioTerm { n: usize, d: usize },
+    /// Leaves were asked for with no leaf at all.
+    NoLeaves,
+    /// A curve was asked for with a tolerance that is not a positive finite number.
+    InvalidTolerance,
+    /// A spiral was asked for with a growth that is not finite, or not positive for a logarithmic spiral.
+    InvalidGrowth,
+    /// A spiral was asked for with a number of turns that is not finite.
+    InvalidTurns,
+    /// A golden rectangle spiral was asked for with a size that is negative or not finite.
+    InvalidSize,
+    /// A radius is negative or not finite, or the inner radius of a ring is larger than its outer radius.
+    InvalidRadius,
+    /// A leaf with a point base was asked for with different start and end points.
+    PointBaseNotAPoint,
+    /// A point of a Sri Yantra was used before it was constructed.
+    MissingPoint(String),
+    /// A palette has no part with this name.
+    MissingPart(String),
+    /// A palette has fewer parts than an art form needs.
+    TooFewParts { needed: usize, found: usize },
+    /// A view box was asked for with an aspect ratio that is not a positive finite number.
+    InvalidAspectRatio,
+    /// A raster image was asked for showing a view box that is empty or not finite.
+    InvalidViewBox,
+    /// A raster image was asked for with no pixels.
+    EmptyImage,
+    /// An image was encoded from a number of pixels that doesn't match its size.
+    PixelCount { expected: usize, found: usize },
+    /// A string that isn't a colour.
+    Color(ParseColorError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoIntersection => write!(f, "intersection not found"),
+            Error::TooFewVertices(n) => {
+                write!(f, "a polygon must have at least three vertices, got {}", n)
+            }
+            Error::ZeroStep => write!(f, "the step q of a star polygon must be positive"),
+            Error::NotCoprime { p, q } => {
+                write!(f, "p = {} and q = {} of an isotoxal star must be coprime", p, q)
+            }
+            Error::NotConcave { p, q } => write!(
+                f,
+                "an isotoxal star with p = {} and q = {} isn't concave, q mod p must be neither 0, 1 nor p - 1",
+                p, q
+            ),
+            Error::ZeroRatioTerm { n, d } => {
+                write!(f, "both terms of the ratio {}/{} must be positive", n, d)
+            }
+            Error::InvalidTolerance => write!(f, "the tolerance must be positive and finite"),
+            Error::InvalidGrowth => write!(
+                f,
+                "the growth of a spiral must be finite, and positive for a logarithmic spiral"
+            ),
+            Error::InvalidTurns => write!(f, "the number of turns must be finite"),
+            Error::InvalidSize => write!(f, "the size must be finite and not negative"),
+            Error::InvalidRadius => write!(
+                f,
+                "a radius must be finite and not negative, and an inner radius at most the outer radius"
+            ),
+            Error::NoLeaves => write!(f, "the number of leaves must be positive"),
+            Error::PointBaseNotAPoint => {
+                write!(f, "start and end must be equal for point base type")
+            }
+            Error::MissingPoint(name) => write!(f, "point {} has not been constructed", name),
+            Error::MissingPart(name) => write!(f, "the palette has no part {}", name),
+            Error::TooFewParts { needed, found } => write!(
+                f,
+                "the palette has {} parts, {} are needed",
+                found, needed
+            ),
+            Error::InvalidAspectRatio => {
+                write!(f, "the aspect ratio must be positive and finite")
+            }
+            Error::InvalidViewBox => {
+                write!(f, "the view box must be finite with a positive width and height")
+            }
+            Error::EmptyImage => write!(f, "an image must be at least one pixel wide and high"),
+            Error::PixelCount { expected, found } => {
+                write!(f, "expected {} pixels, found {}", expected, found)
+            }
+            Error::Color(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Color(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseColorError> for Error {
+    fn from(error: ParseColorError) -> Self {
+        Error::Color(error)
+    }
+}
+
+/// Conversions that can't fail, like those of typed paints, never give an error.
+impl From<std::convert::Infallible> for Error {
+    fn from(error: std::convert::Infallible) -> Self {
+        match error {}
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Unwrap a result of a `try_` variant, panicking with the message of its error.
+pub(crate) fn or_panic<T>(result: Result<T>) -> T {
+    result.unwrap_or_else(|error| panic!("{}", error))
+}
@@ -1,12 +1,31 @@
-use svg::Document;
-pub type Canvas = Document;
+pub type Canvas = canvas::SvgCanvas;
 pub mod art_forms;
+pub mod backend;
 pub mod canvas;
+pub mod color;
+pub(crate) mod defs;
+pub mod error;
+pub mod paint;
+pub mod path;
+pub mod scene;
 pub(crate) mod utils;
 
 pub mod prelude {
-    pub use super::art_forms::base_shapes::{BaseShapes, Config, SetConfig};
+    pub use super::art_forms::base_shapes::{
+        BaseShapes, Config, Corner, Edge, FillRule, LineCap, LineJoin, Placement, Roulette, SetConfig,
+        Spiral, Spline,
+    };
     pub use super::art_forms::leaves::{Leaves, LeafStyle, LeafStyleDetailed};
     pub use super::art_forms::yantra::{Yantra};
+    pub use super::canvas::Canvas as _;
+    pub use super::canvas::Annotate as _;
+    pub use super::canvas::{ClipShape, LayeredCanvas, Metadata, Unit, YAxis};
+    pub use super::color::traditional_colors::Culture;
+    pub use super::color::{Color, ColorSpace, Palette, Theme, Themed};
+    pub use super::backend::{Backend, RasterCanvas};
+    pub use super::paint::{LinearGradient, Paint, Pattern, RadialGradient};
+    pub use super::path::Path;
+    pub use super::scene::{Geometry, Scene, Shape};
+    pub use super::error::Error;
     pub use super::Canvas;
 }
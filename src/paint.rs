@@ -0,0 +1,156 @@
+//! Backend independent description of how shapes are filled and stroked:
+//! a plain colour, a gradient or a pattern of repeated tiles.
+//! Gradients and patterns are given in the coordinates of the art forms.
+use derive_new::new;
+use geo::{Point, Rect};
+use geo_types::CoordFloat;
+
+use crate::color::{Color, ParseColorError};
+use crate::defs::definition_id;
+use crate::scene::Scene;
+
+/// A colour at a position along a gradient.
+#[derive(Clone, Debug, PartialEq, new)]
+pub struct GradientStop<T: CoordFloat> {
+    /// Position along the gradient, from `0` to `1`.
+    pub offset: T,
+    pub color: Color,
+}
+
+/// A gradient along the line from `start` to `end`, padded with the end colours beyond it.
+#[derive(Clone, Debug, PartialEq, new)]
+pub struct LinearGradient<T: CoordFloat> {
+    pub start: Point<T>,
+    pub end: Point<T>,
+    #[new(default)]
+    pub stops: Vec<GradientStop<T>>,
+}
+
+/// A gradient from `focus` (the center unless set otherwise) out to the circle
+/// of `radius` around `center`, padded with the outer colour beyond it.
+#[derive(Clone, Debug, PartialEq, new)]
+pub struct RadialGradient<T: CoordFloat> {
+    pub center: Point<T>,
+    pub radius: T,
+    #[new(default)]
+    pub focus: Option<Point<T>>,
+    #[new(default)]
+    pub stops: Vec<GradientStop<T>>,
+}
+
+/// Copies of a tile repeated over the plane.
+/// The content of the tile is drawn with the art forms on a [`Scene`], in the same
+/// coordinates as `tile`; whatever is outside of `tile` is cut off.
+#[derive(Clone, Debug, PartialEq, new)]
+pub struct Pattern<T: CoordFloat> {
+    pub tile: Rect<T>,
+    pub content: Scene<T>,
+}
+
+impl<T: CoordFloat> LinearGradient<T> {
+    /// Add a stop of `color` at `offset`, stops are expected in increasing order.
+    pub fn stop(mut self, offset: T, color: Color) -> Self {
+        self.stops.push(GradientStop::new(offset, color));
+        self
+    }
+}
+
+impl<T: CoordFloat> RadialGradient<T> {
+    /// Add a stop of `color` at `offset`, stops are expected in increasing order.
+    pub fn stop(mut self, offset: T, color: Color) -> Self {
+        self.stops.push(GradientStop::new(offset, color));
+        self
+    }
+
+    /// Start the gradient at `focus` instead of the center.
+    pub fn focus(mut self, focus: Point<T>) -> Self {
+        self.focus = Some(focus);
+        self
+    }
+}
+
+/// How a shape is filled or stroked.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Paint<T: CoordFloat> {
+    /// Nothing is painted, `none` in svg.
+    None,
+    Color(Color),
+    LinearGradient(LinearGradient<T>),
+    RadialGradient(RadialGradient<T>),
+    Pattern(Pattern<T>),
+}
+
+impl<T: CoordFloat> Paint<T> {
+    /// Whether nothing is painted.
+    pub fn is_none(&self) -> bool {
+        matches!(self, Paint::None)
+    }
+
+    /// Id of the definition of a gradient or pattern, identical paints get identical ids.
+    pub(crate) fn definition_id(&self) -> Option<String> {
+        match self {
+            Paint::None | Paint::Color(_) => None,
+            Paint::Pattern(_) => Some(definition_id("pattern", &format!("{:?}", self))),
+            _ => Some(definition_id("gradient", &format!("{:?}", self))),
+        }
+    }
+
+    /// Value of the svg `fill` or `stroke` attribute.
+    /// Gradients and patterns are referenced by their id, their definition is added by the svg backend.
+    pub fn svg_value(&self) -> String {
+        match (self, self.definition_id()) {
+            (Paint::None, _) => "none".to_string(),
+            (Paint::Color(color), _) => color.to_string(),
+            (_, id) => format!("url(#{})", id.unwrap_or_default()),
+        }
+    }
+}
+
+impl<T: CoordFloat> std::str::FromStr for Paint<T> {
+    type Err = ParseColorError;
+
+    /// Parse `none` or a colour.
+    fn from_str(paint: &str) -> Result<Self, Self::Err> {
+        match paint.trim() {
+            "none" => Ok(Paint::None),
+            color => color.parse().map(Paint::Color),
+        }
+    }
+}
+
+impl<T: CoordFloat> TryFrom<&str> for Paint<T> {
+    type Error = ParseColorError;
+
+    fn try_from(paint: &str) -> Result<Self, Self::Error> {
+        paint.parse()
+    }
+}
+
+impl<T: CoordFloat> From<Color> for Paint<T> {
+    fn from(color: Color) -> Self {
+        Paint::Color(color)
+    }
+}
+impl<T: CoordFloat> From<LinearGradient<T>> for Paint<T> {
+    fn from(gradient: LinearGradient<T>) -> Self {
+        Paint::LinearGradient(gradient)
+    }
+}
+
+impl<T: CoordFloat> From<RadialGradient<T>> for Paint<T> {
+    fn from(gradient: RadialGradient<T>) -> Self {
+        Paint::RadialGradient(gradient)
+    }
+}
+
+impl<T: CoordFloat> From<Pattern<T>> for Paint<T> {
+    fn from(pattern: Pattern<T>) -> Self {
+        Paint::Pattern(pattern)
+    }
+}
+
+impl<T: CoordFloat> PartialEq<&str> for Paint<T> {
+    fn eq(&self, paint: &&str) -> bool {
+        paint.parse().is_ok_and(|paint: Paint<T>| *self == paint)
+    }
+}
@@ -0,0 +1,315 @@
+//! Geometry of a [`Path`]: its length, the points, tangents and normals along it,
+//! its flattening into line strings, its reverse and its bounds.
+//! Arcs are measured on their cubic Bezier approximation.
+//! A [`PathMeasure`] keeps the lengths of the segments of a path for repeated queries.
+use geo::{Coord, LineString, Point, Rect};
+use geo_types::CoordFloat;
+use std::marker::PhantomData;
+
+use super::{Path, PathCommand};
+use crate::path::segment::{path_pieces, path_sub_paths, union, Segment};
+
+fn to_point<T: CoordFloat>(coord: Coord) -> Point<T> {
+    Point::new(T::from(coord.x).unwrap(), T::from(coord.y).unwrap())
+}
+
+/// End point of a drawing command.
+fn end_point<T: CoordFloat>(command: &PathCommand<T>) -> Option<Point<T>> {
+    match *command {
+        PathCommand::MoveTo(end)
+        | PathCommand::LineTo(end)
+        | PathCommand::QuadraticTo(_, end)
+        | PathCommand::CubicTo(_, _, end)
+        | PathCommand::ArcTo(_, _, _, _, _, end) => Some(end),
+        PathCommand::Close => None,
+    }
+}
+
+/// Continue `path` along `command` drawn backwards, to the point `from` it started at.
+fn back_to<T: CoordFloat>(path: Path<T>, from: Point<T>, command: PathCommand<T>) -> Path<T> {
+    match command {
+        PathCommand::LineTo(_) => path.line_to(from),
+        PathCommand::QuadraticTo(control, _) => path.quadratic_to(control, from),
+        PathCommand::CubicTo(control_1, control_2, _) => path.cubic_to(control_2, control_1, from),
+        PathCommand::ArcTo(rx, ry, x_rotation, large_arc, sweep, _) => {
+            path.arc_to((rx, ry), x_rotation, large_arc, !sweep, from)
+        }
+        PathCommand::MoveTo(_) | PathCommand::Close => path,
+    }
+}
+
+/// The drawing commands of a sub-path, each with the point it starts from.
+struct SubPathCommands<T: CoordFloat> {
+    start: Point<T>,
+    commands: Vec<(Point<T>, PathCommand<T>)>,
+    closed: bool,
+}
+
+/// The segments of a [`Path`] with their lengths, measured once, to find points along the path.
+///
+/// Points are found either at a distance along the path or at a parameter.
+/// The parameter runs from `0` at the start of the path to `n` at its end, `n` being the number
+/// of pieces the path draws: its lines, curves, arcs and closing lines, moves excluded.
+/// Its integer part picks a piece and its fractional part is the parameter within the piece,
+/// the Bezier parameter of a line or a curve, or the fraction of the sweep of an arc.
+/// Parameters out of the path are clamped to its ends.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathMeasure<T: CoordFloat> {
+    segments: Vec<Segment>,
+    /// Length of the path up to the end of each segment.
+    ends: Vec<f64>,
+    /// Index of the first segment of each piece, followed by the number of segments.
+    pieces: Vec<usize>,
+    marker: PhantomData<T>,
+}
+
+impl<T: CoordFloat> PathMeasure<T> {
+    /// Measure the segments of `path`.
+    pub fn new(path: &Path<T>) -> Self {
+        let mut segments = vec![];
+        let mut pieces = vec![];
+        for piece in path_pieces(path) {
+            pieces.push(segments.len());
+            segments.extend(piece);
+        }
+        pieces.push(segments.len());
+        let ends = segments
+            .iter()
+            .scan(0.0, |length, segment| {
+                *length += segment.length();
+                Some(*length)
+            })
+            .collect();
+        PathMeasure {
+            segments,
+            ends,
+            pieces,
+            marker: PhantomData,
+        }
+    }
+
+    /// Total length of the path, moves between sub-paths excluded.
+    pub fn length(&self) -> T {
+        T::from(self.ends.last().copied().unwrap_or(0.0)).unwrap()
+    }
+
+    /// Number of pieces the path draws, the end of the range of the parameter.
+    pub fn pieces(&self) -> usize {
+        self.pieces.len() - 1
+    }
+
+    /// The segment at `distance` along the path and the parameter there,
+    /// `distance` being clamped to the path.
+    fn segment_at_length(&self, distance: T) -> Option<(Segment, f64)> {
+        let last = *self.segments.last()?;
+        let distance = distance.to_f64().unwrap().max(0.0);
+        let index = self.ends.partition_point(|&end| end < distance);
+        match self.segments.get(index) {
+            Some(segment) => {
+                let start = index.checked_sub(1).map_or(0.0, |before| self.ends[before]);
+                Some((*segment, segment.parameter_at_length(distance - start)))
+            }
+            None => Some((last, 1.0)),
+        }
+    }
+
+    /// The segment at parameter `t` of the path and the parameter within the segment.
+    fn segment_at(&self, t: T) -> Option<(Segment, f64)> {
+        let last = *self.segments.last()?;
+        let t = t.to_f64().unwrap().max(0.0);
+        let piece = t.floor() as usize;
+        if piece >= self.pieces() {
+            return Some((last, 1.0));
+        }
+        let (first, end) = (self.pieces[piece], self.pieces[piece + 1]);
+        let count = end - first;
+        let within = (t - piece as f64) * count as f64;
+        let index = (within.floor() as usize).min(count - 1);
+        Some((self.segments[first + index], within - index as f64))
+    }
+
+    /// The point at `distance` along the path from its start, `None` for an empty path.
+    /// Distances out of the path are clamped to its ends.
+    pub fn point_at_length(&self, distance: T) -> Option<Point<T>> {
+        let (segment, t) = self.segment_at_length(distance)?;
+        Some(to_point(segment.point_at(t)))
+    }
+
+    /// The unit tangent, in the direction of the path, at `distance` along the path from its start.
+    pub fn tangent_at_length(&self, distance: T) -> Option<Point<T>> {
+        let (segment, t) = self.segment_at_length(distance)?;
+        segment.tangent_at(t).map(to_point)
+    }
+
+    /// The unit normal at `distance` along the path, the tangent turned a quarter counter-clockwise.
+    /// It points outwards on a clockwise path and inwards on a counter-clockwise one (y pointing up).
+    pub fn normal_at_length(&self, distance: T) -> Option<Point<T>> {
+        self.tangent_at_length(distance).map(quarter_turn)
+    }
+
+    /// The point at parameter `t` of the path, `None` for an empty path.
+    pub fn point_at(&self, t: T) -> Option<Point<T>> {
+        let (segment, t) = self.segment_at(t)?;
+        Some(to_point(segment.point_at(t)))
+    }
+
+    /// The unit tangent, in the direction of the path, at parameter `t` of the path.
+    pub fn tangent_at(&self, t: T) -> Option<Point<T>> {
+        let (segment, t) = self.segment_at(t)?;
+        segment.tangent_at(t).map(to_point)
+    }
+
+    /// The unit normal at parameter `t` of the path, the tangent turned a quarter counter-clockwise.
+    pub fn normal_at(&self, t: T) -> Option<Point<T>> {
+        self.tangent_at(t).map(quarter_turn)
+    }
+}
+
+/// A vector turned a quarter counter-clockwise (y pointing up).
+fn quarter_turn<T: CoordFloat>(vector: Point<T>) -> Point<T> {
+    Point::new(-vector.y(), vector.x())
+}
+
+impl<T: CoordFloat> Path<T> {
+    /// Segments of all the sub-paths, the closing lines included.
+    fn segments(&self) -> Vec<Segment> {
+        path_sub_paths(self)
+            .into_iter()
+            .flat_map(|sub_path| sub_path.segments)
+            .collect()
+    }
+
+    /// Measure the path once for many points along it, see [`PathMeasure`].
+    pub fn measure(&self) -> PathMeasure<T> {
+        PathMeasure::new(self)
+    }
+
+    /// Total length of the sub-paths, moves between them excluded.
+    pub fn length(&self) -> T {
+        T::from(self.segments().iter().map(Segment::length).sum::<f64>()).unwrap()
+    }
+
+    /// The point at `distance` along the path from its start, `None` for an empty path.
+    /// Distances out of the path are clamped to its ends.
+    pub fn point_at_length(&self, distance: T) -> Option<Point<T>> {
+        self.measure().point_at_length(distance)
+    }
+
+    /// The unit tangent, in the direction of the path, at `distance` along the path from its start.
+    pub fn tangent_at_length(&self, distance: T) -> Option<Point<T>> {
+        self.measure().tangent_at_length(distance)
+    }
+
+    /// The unit normal at `distance` along the path, the tangent turned a quarter counter-clockwise.
+    /// It points outwards on a clockwise path and inwards on a counter-clockwise one (y pointing up).
+    pub fn normal_at_length(&self, distance: T) -> Option<Point<T>> {
+        self.measure().normal_at_length(distance)
+    }
+
+    /// The point at parameter `t` of the path, see [`PathMeasure`] for the parameter.
+    pub fn point_at(&self, t: T) -> Option<Point<T>> {
+        self.measure().point_at(t)
+    }
+
+    /// The unit tangent, in the direction of the path, at parameter `t` of the path.
+    pub fn tangent_at(&self, t: T) -> Option<Point<T>> {
+        self.measure().tangent_at(t)
+    }
+
+    /// The unit normal at parameter `t` of the path, the tangent turned a quarter counter-clockwise.
+    pub fn normal_at(&self, t: T) -> Option<Point<T>> {
+        self.measure().normal_at(t)
+    }
+
+    /// One line string through every sub-path, at most `tolerance` away from it.
+    /// A path is split where a `move_to` starts a new sub-path or where drawing goes on after a `close`,
+    /// so a continuous path gives a single line string. Closed sub-paths give closed line strings.
+    pub fn flatten(&self, tolerance: T) -> Vec<LineString<T>> {
+        let tolerance = tolerance.to_f64().unwrap();
+        path_sub_paths(self)
+            .into_iter()
+            .filter_map(|sub_path| {
+                let mut coords = vec![sub_path.segments.first()?.start()];
+                for segment in &sub_path.segments {
+                    segment.flatten_into(tolerance, &mut coords);
+                }
+                let line_string: LineString<T> = coords.into_iter().map(to_point).collect();
+                Some(line_string)
+            })
+            .collect()
+    }
+
+    /// Smallest rectangle containing the path, `None` for a path that draws nothing.
+    pub fn bounds(&self) -> Option<Rect<T>> {
+        let rect = self.segments().iter().map(Segment::bounds).reduce(union)?;
+        Some(Rect::new(
+            to_point::<T>(rect.min()),
+            to_point::<T>(rect.max()),
+        ))
+    }
+
+    /// The same path drawn the other way: sub-paths in reverse order, each from its end to its start.
+    /// Arcs stay arcs, with their sweep flipped.
+    pub fn reverse(&self) -> Self {
+        let mut sub_paths: Vec<SubPathCommands<T>> = vec![];
+        let mut point = Point::new(T::zero(), T::zero());
+        for command in self.commands() {
+            match command {
+                PathCommand::MoveTo(start) => {
+                    sub_paths.push(SubPathCommands {
+                        start: *start,
+                        commands: vec![],
+                        closed: false,
+                    });
+                    point = *start;
+                }
+                PathCommand::Close => {
+                    if let Some(sub_path) = sub_paths.last_mut() {
+                        sub_path.closed = true;
+                        point = sub_path.start;
+                    }
+                }
+                command => {
+                    // drawing after a close starts a new sub-path where the closed one started
+                    let open = matches!(sub_paths.last(), Some(sub_path) if !sub_path.closed);
+                    if !open {
+                        sub_paths.push(SubPathCommands {
+                            start: point,
+                            commands: vec![],
+                            closed: false,
+                        });
+                    }
+                    let sub_path = sub_paths.last_mut().unwrap();
+                    sub_path.commands.push((point, command.clone()));
+                    point = end_point(command).unwrap();
+                }
+            }
+        }
+
+        sub_paths
+            .into_iter()
+            .rev()
+            .fold(Path::new(), |path, sub_path| {
+                let end = sub_path
+                    .commands
+                    .last()
+                    .and_then(|(_, command)| end_point(command))
+                    .unwrap_or(sub_path.start);
+                let path = match sub_path.closed && end != sub_path.start {
+                    // the closing line is drawn first
+                    true => path.move_to(sub_path.start).line_to(end),
+                    false if sub_path.closed => path.move_to(sub_path.start),
+                    false => path.move_to(end),
+                };
+                let path = sub_path
+                    .commands
+                    .into_iter()
+                    .rev()
+                    .fold(path, |path, (from, command)| back_to(path, from, command));
+                match sub_path.closed {
+                    true => path.close(),
+                    false => path,
+                }
+            })
+    }
+}
@@ -0,0 +1,9 @@
+#[allow(clippy::module_inception)]
+pub mod path;
+pub use path::{Path, PathCommand};
+pub mod geometry;
+pub use geometry::PathMeasure;
+pub(crate) mod segment;
+
+#[cfg(test)]
+pub mod test_path;
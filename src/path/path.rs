@@ -0,0 +1,101 @@
+//! Backend independent description of a path.
+//! A path is a sequence of drawing commands with absolute coordinates,
+//! built with a builder pattern API just like svg path data.
+use geo::Point;
+use geo_types::CoordFloat;
+
+/// A single command of a [`Path`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathCommand<T: CoordFloat> {
+    /// Start a new sub-path at the point.
+    MoveTo(Point<T>),
+    /// Straight line to the point.
+    LineTo(Point<T>),
+    /// Quadratic Bezier curve with a control point and an end point.
+    QuadraticTo(Point<T>, Point<T>),
+    /// Cubic Bezier curve with two control points and an end point.
+    CubicTo(Point<T>, Point<T>, Point<T>),
+    /// Elliptical arc as in svg: radii, x-axis rotation in degrees,
+    /// large arc flag, sweep flag and the end point.
+    /// The sweep flag is `true` for counter-clockwise arcs (y pointing up).
+    ArcTo(T, T, T, bool, bool, Point<T>),
+    /// Close the current sub-path.
+    Close,
+}
+
+/// A path made of lines, Bezier curves and arcs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path<T: CoordFloat> {
+    commands: Vec<PathCommand<T>>,
+}
+
+impl<T: CoordFloat> Default for Path<T> {
+    fn default() -> Self {
+        Path { commands: vec![] }
+    }
+}
+
+impl<T: CoordFloat> Path<T> {
+    /// Create an empty path.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Commands of the path.
+    pub fn commands(&self) -> &[PathCommand<T>] {
+        &self.commands
+    }
+
+    fn add(mut self, command: PathCommand<T>) -> Self {
+        self.commands.push(command);
+        self
+    }
+
+    pub fn move_to(self, point: Point<T>) -> Self {
+        self.add(PathCommand::MoveTo(point))
+    }
+
+    pub fn line_to(self, point: Point<T>) -> Self {
+        self.add(PathCommand::LineTo(point))
+    }
+
+    pub fn quadratic_to(self, control: Point<T>, end: Point<T>) -> Self {
+        self.add(PathCommand::QuadraticTo(control, end))
+    }
+
+    pub fn cubic_to(self, control_1: Point<T>, control_2: Point<T>, end: Point<T>) -> Self {
+        self.add(PathCommand::CubicTo(control_1, control_2, end))
+    }
+
+    pub fn arc_to(
+        self,
+        radii: (T, T),
+        x_rotation: T,
+        large_arc: bool,
+        sweep: bool,
+        end: Point<T>,
+    ) -> Self {
+        self.add(PathCommand::ArcTo(
+            radii.0, radii.1, x_rotation, large_arc, sweep, end,
+        ))
+    }
+
+    pub fn close(self) -> Self {
+        self.add(PathCommand::Close)
+    }
+
+    /// A path through the points, closed if `should_close` is set.
+    pub fn from_points(points: Vec<Point<T>>, should_close: bool) -> Self {
+        let mut points = points.into_iter();
+        let path = match points.next() {
+            Some(start) => points.fold(Path::new().move_to(start), |path, point| {
+                path.line_to(point)
+            }),
+            None => Path::new(),
+        };
+        match should_close {
+            true => path.close(),
+            false => path,
+        }
+    }
+}
@@ -0,0 +1,559 @@
+//! Segments of paths.
+//! Paths are turned into line and Bezier segments so that they can be measured.
+use geo::{AffineTransform, Coord, Point, Rect};
+use geo_types::CoordFloat;
+
+use crate::path::{Path, PathCommand};
+
+/// A segment of a path.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Segment {
+    Line(Coord, Coord),
+    Quadratic(Coord, Coord, Coord),
+    Cubic(Coord, Coord, Coord, Coord),
+}
+
+/// A connected piece of a path.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct SubPath {
+    pub segments: Vec<Segment>,
+    pub closed: bool,
+}
+
+/// Smallest rectangle containing both rectangles.
+pub(crate) fn union(rect: Rect, other: Rect) -> Rect {
+    Rect::new(
+        Coord {
+            x: rect.min().x.min(other.min().x),
+            y: rect.min().y.min(other.min().y),
+        },
+        Coord {
+            x: rect.max().x.max(other.max().x),
+            y: rect.max().y.max(other.max().y),
+        },
+    )
+}
+
+/// Rectangle common to both rectangles, if any.
+pub(crate) fn intersection(rect: Rect, other: Rect) -> Option<Rect> {
+    let min = Coord {
+        x: rect.min().x.max(other.min().x),
+        y: rect.min().y.max(other.min().y),
+    };
+    let max = Coord {
+        x: rect.max().x.min(other.max().x),
+        y: rect.max().y.min(other.max().y),
+    };
+    match min.x <= max.x && min.y <= max.y {
+        true => Some(Rect::new(min, max)),
+        false => None,
+    }
+}
+
+/// Bounding rectangle of some coordinates.
+pub(crate) fn coords_bounds(coords: impl IntoIterator<Item = Coord>) -> Option<Rect> {
+    coords
+        .into_iter()
+        .map(|coord| Rect::new(coord, coord))
+        .reduce(union)
+}
+
+/// Roots in `[0, 1]` of `a t^2 + b t + c`.
+fn unit_roots(a: f64, b: f64, c: f64) -> Vec<f64> {
+    let roots = match a.abs() < 1e-12 {
+        true => match b.abs() < 1e-12 {
+            true => vec![],
+            false => vec![-c / b],
+        },
+        false => {
+            let discriminant = b * b - 4.0 * a * c;
+            match discriminant < 0.0 {
+                true => vec![],
+                false => {
+                    let sqrt = discriminant.sqrt();
+                    vec![(-b + sqrt) / (2.0 * a), (-b - sqrt) / (2.0 * a)]
+                }
+            }
+        }
+    };
+    roots
+        .into_iter()
+        .filter(|t| (0.0..=1.0).contains(t))
+        .collect()
+}
+
+impl Segment {
+    pub fn start(&self) -> Coord {
+        match *self {
+            Segment::Line(p, _) | Segment::Quadratic(p, _, _) | Segment::Cubic(p, _, _, _) => p,
+        }
+    }
+
+    pub fn end(&self) -> Coord {
+        match *self {
+            Segment::Line(_, q) | Segment::Quadratic(_, _, q) | Segment::Cubic(_, _, _, q) => q,
+        }
+    }
+
+    /// Point at parameter `t` in `[0, 1]`.
+    pub fn point_at(&self, t: f64) -> Coord {
+        let s = 1.0 - t;
+        match *self {
+            Segment::Line(p0, p1) => p0 * s + p1 * t,
+            Segment::Quadratic(p0, p1, p2) => p0 * (s * s) + p1 * (2.0 * s * t) + p2 * (t * t),
+            Segment::Cubic(p0, p1, p2, p3) => {
+                p0 * (s * s * s)
+                    + p1 * (3.0 * s * s * t)
+                    + p2 * (3.0 * s * t * t)
+                    + p3 * (t * t * t)
+            }
+        }
+    }
+
+    /// Derivative with respect to the parameter at `t` in `[0, 1]`.
+    pub fn derivative_at(&self, t: f64) -> Coord {
+        let s = 1.0 - t;
+        match *self {
+            Segment::Line(p0, p1) => p1 - p0,
+            Segment::Quadratic(p0, p1, p2) => (p1 - p0) * (2.0 * s) + (p2 - p1) * (2.0 * t),
+            Segment::Cubic(p0, p1, p2, p3) => {
+                (p1 - p0) * (3.0 * s * s) + (p2 - p1) * (6.0 * s * t) + (p3 - p2) * (3.0 * t * t)
+            }
+        }
+    }
+
+    /// Unit tangent at `t` in `[0, 1]`. Where the derivative vanishes, e.g. at a control point
+    /// on an end point, the direction is taken from the points around `t`.
+    pub fn tangent_at(&self, t: f64) -> Option<Coord> {
+        let unit = |coord: Coord| {
+            let length = coord.x.hypot(coord.y);
+            (length > 1e-12).then(|| coord / length)
+        };
+        unit(self.derivative_at(t)).or_else(|| {
+            let (before, after) = ((t - 1e-6).max(0.0), (t + 1e-6).min(1.0));
+            unit(self.point_at(after) - self.point_at(before))
+        })
+    }
+
+    /// Control points, from the start to the end.
+    fn controls(&self) -> Vec<Coord> {
+        match *self {
+            Segment::Line(p0, p1) => vec![p0, p1],
+            Segment::Quadratic(p0, p1, p2) => vec![p0, p1, p2],
+            Segment::Cubic(p0, p1, p2, p3) => vec![p0, p1, p2, p3],
+        }
+    }
+
+    /// Arc length of the segment.
+    /// Curves are halved until their control polygon is about as long as their chord.
+    pub fn length(&self) -> f64 {
+        self.length_within(0)
+    }
+
+    fn length_within(&self, depth: usize) -> f64 {
+        let controls = self.controls();
+        let distance = |pair: &[Coord]| (pair[1] - pair[0]).x.hypot((pair[1] - pair[0]).y);
+        let chord = distance(&[self.start(), self.end()]);
+        let polygon: f64 = controls.windows(2).map(distance).sum();
+        match polygon - chord <= 1e-9 * polygon.max(1.0) || depth >= 24 {
+            // Gravesen's estimate, between the chord and the control polygon
+            true => {
+                let degree = (controls.len() - 1) as f64;
+                (2.0 * chord + (degree - 1.0) * polygon) / (degree + 1.0)
+            }
+            false => {
+                let (first, second) = self.split(0.5);
+                first.length_within(depth + 1) + second.length_within(depth + 1)
+            }
+        }
+    }
+
+    /// Parameter at which the segment is `distance` long, `distance` being clamped to the segment.
+    /// Newton steps on the length of the first part of the segment, kept within a bracket
+    /// that is halved whenever a step would leave it.
+    pub fn parameter_at_length(&self, distance: f64) -> f64 {
+        let length = self.length();
+        let distance = distance.clamp(0.0, length);
+        let tolerance = 1e-9 * length.max(1.0);
+        let (mut low, mut high) = (0.0, 1.0);
+        let mut t = match length > 0.0 {
+            true => distance / length,
+            false => return 0.0,
+        };
+        for _ in 0..50 {
+            let error = self.split(t).0.length() - distance;
+            if error.abs() <= tolerance {
+                break;
+            }
+            match error < 0.0 {
+                true => low = t,
+                false => high = t,
+            }
+            let speed = self.derivative_at(t);
+            let step = t - error / speed.x.hypot(speed.y);
+            t = match low < step && step < high {
+                true => step,
+                false => (low + high) / 2.0,
+            };
+        }
+        t
+    }
+
+    /// Whether the control points, and so the whole segment, are within `tolerance` of the chord.
+    fn is_flat(&self, tolerance: f64) -> bool {
+        let (start, end) = (self.start(), self.end());
+        let chord = end - start;
+        let length = chord.x.hypot(chord.y);
+        self.controls().iter().all(|control| {
+            let offset = *control - start;
+            match length > 1e-12 {
+                true => {
+                    let across = (chord.x * offset.y - chord.y * offset.x).abs() / length;
+                    let along = (chord.x * offset.x + chord.y * offset.y) / length;
+                    across <= tolerance && -tolerance <= along && along <= length + tolerance
+                }
+                false => offset.x.hypot(offset.y) <= tolerance,
+            }
+        })
+    }
+
+    /// Push the end points of lines at most `tolerance` away from the segment, the start excluded.
+    pub fn flatten_into(&self, tolerance: f64, coords: &mut Vec<Coord>) {
+        self.flatten_within(tolerance, coords, 0)
+    }
+
+    fn flatten_within(&self, tolerance: f64, coords: &mut Vec<Coord>, depth: usize) {
+        if depth >= 24 || self.is_flat(tolerance) {
+            coords.push(self.end());
+            return;
+        }
+        let (first, second) = self.split(0.5);
+        first.flatten_within(tolerance, coords, depth + 1);
+        second.flatten_within(tolerance, coords, depth + 1);
+    }
+
+    /// Parameters in `(0, 1)` where the segment is extremal in x or y.
+    fn extrema(&self) -> Vec<f64> {
+        match *self {
+            Segment::Line(..) => vec![],
+            Segment::Quadratic(p0, p1, p2) => {
+                let roots = |a: f64, b: f64, c: f64| {
+                    unit_roots(0.0, 2.0 * (a - 2.0 * b + c), 2.0 * (b - a))
+                };
+                let mut ts = roots(p0.x, p1.x, p2.x);
+                ts.extend(roots(p0.y, p1.y, p2.y));
+                ts
+            }
+            Segment::Cubic(p0, p1, p2, p3) => {
+                let roots = |a: f64, b: f64, c: f64, d: f64| {
+                    unit_roots(
+                        3.0 * (-a + 3.0 * b - 3.0 * c + d),
+                        6.0 * (a - 2.0 * b + c),
+                        3.0 * (b - a),
+                    )
+                };
+                let mut ts = roots(p0.x, p1.x, p2.x, p3.x);
+                ts.extend(roots(p0.y, p1.y, p2.y, p3.y));
+                ts
+            }
+        }
+    }
+
+    /// Exact bounding rectangle of the segment.
+    pub fn bounds(&self) -> Rect {
+        let extrema = self.extrema().into_iter().map(|t| self.point_at(t));
+        coords_bounds([self.start(), self.end()].into_iter().chain(extrema)).unwrap()
+    }
+
+    /// The two halves of the segment, split at parameter `t`.
+    pub fn split(&self, t: f64) -> (Segment, Segment) {
+        let lerp = |p: Coord, q: Coord| p * (1.0 - t) + q * t;
+        match *self {
+            Segment::Line(p0, p1) => {
+                let p = lerp(p0, p1);
+                (Segment::Line(p0, p), Segment::Line(p, p1))
+            }
+            Segment::Quadratic(p0, p1, p2) => {
+                let (q0, q1) = (lerp(p0, p1), lerp(p1, p2));
+                let p = lerp(q0, q1);
+                (Segment::Quadratic(p0, q0, p), Segment::Quadratic(p, q1, p2))
+            }
+            Segment::Cubic(p0, p1, p2, p3) => {
+                let (q0, q1, q2) = (lerp(p0, p1), lerp(p1, p2), lerp(p2, p3));
+                let (r0, r1) = (lerp(q0, q1), lerp(q1, q2));
+                let p = lerp(r0, r1);
+                (Segment::Cubic(p0, q0, r0, p), Segment::Cubic(p, r1, q2, p3))
+            }
+        }
+    }
+
+    /// Whether the segment comes within `distance` of `rect`.
+    /// The segment is split until its pieces are either clearly apart from or touching the rectangle.
+    pub fn meets_rect(&self, rect: Rect, distance: f64) -> bool {
+        let near = |coord: Coord| {
+            let dx = (rect.min().x - coord.x)
+                .max(coord.x - rect.max().x)
+                .max(0.0);
+            let dy = (rect.min().y - coord.y)
+                .max(coord.y - rect.max().y)
+                .max(0.0);
+            dx.hypot(dy) <= distance
+        };
+        let margin = Coord {
+            x: distance,
+            y: distance,
+        };
+        let bounds = self.bounds();
+        match intersection(bounds, Rect::new(rect.min() - margin, rect.max() + margin)) {
+            None => false,
+            Some(_) if near(self.start()) || near(self.end()) => true,
+            Some(_) if bounds.width().max(bounds.height()) < 1e-9 => false,
+            Some(_) => {
+                let (first, second) = self.split(0.5);
+                first.meets_rect(rect, distance) || second.meets_rect(rect, distance)
+            }
+        }
+    }
+
+    /// Signed number of times the segment crosses the horizontal ray from `point` towards +x,
+    /// upward crossings counting positive.
+    fn crossings(&self, point: Coord) -> i32 {
+        // the extrema split the segment into y-monotone pieces
+        let mut ts = vec![0.0];
+        ts.extend(self.extrema());
+        ts.push(1.0);
+        ts.sort_by(|t, u| t.partial_cmp(u).unwrap());
+        ts.windows(2)
+            .map(|pair| {
+                let (mut low, mut high) = (pair[0], pair[1]);
+                let (start, end) = (self.point_at(low), self.point_at(high));
+                // half-open in y, so that shared end points are counted once
+                let upward = start.y <= point.y && point.y < end.y;
+                let downward = end.y <= point.y && point.y < start.y;
+                if !upward && !downward {
+                    return 0;
+                }
+                for _ in 0..60 {
+                    let middle = (low + high) / 2.0;
+                    match (self.point_at(middle).y < point.y) == upward {
+                        true => low = middle,
+                        false => high = middle,
+                    }
+                }
+                match (self.point_at((low + high) / 2.0).x > point.x, upward) {
+                    (false, _) => 0,
+                    (true, true) => 1,
+                    (true, false) => -1,
+                }
+            })
+            .sum()
+    }
+
+    /// Segment mapped by an affine transform.
+    pub fn transformed(&self, transform: &AffineTransform) -> Segment {
+        let apply = |coord| transform.apply(coord);
+        match *self {
+            Segment::Line(p0, p1) => Segment::Line(apply(p0), apply(p1)),
+            Segment::Quadratic(p0, p1, p2) => Segment::Quadratic(apply(p0), apply(p1), apply(p2)),
+            Segment::Cubic(p0, p1, p2, p3) => {
+                Segment::Cubic(apply(p0), apply(p1), apply(p2), apply(p3))
+            }
+        }
+    }
+}
+
+impl SubPath {
+    /// Winding number of the sub-path around `point`, the sub-path being closed as it is for filling.
+    pub fn winding_number(&self, point: Coord) -> i32 {
+        let closing = match (self.segments.first(), self.segments.last()) {
+            (Some(first), Some(last)) if first.start() != last.end() => {
+                Some(Segment::Line(last.end(), first.start()))
+            }
+            _ => None,
+        };
+        self.segments
+            .iter()
+            .chain(closing.iter())
+            .map(|segment| segment.crossings(point))
+            .sum()
+    }
+}
+
+/// Cubic Bezier approximation of an svg elliptical arc, see the
+/// [implementation notes](https://www.w3.org/TR/SVG/implnote.html#ArcImplementationNotes).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn arc_to_cubics(
+    from: Coord,
+    rx: f64,
+    ry: f64,
+    x_rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+    to: Coord,
+) -> Vec<Segment> {
+    if from == to {
+        return vec![];
+    }
+    let (mut rx, mut ry) = (rx.abs(), ry.abs());
+    if rx == 0.0 || ry == 0.0 {
+        return vec![Segment::Line(from, to)];
+    }
+    let (sin_phi, cos_phi) = x_rotation.to_radians().sin_cos();
+    let half = (from - to) / 2.0;
+    let x1 = cos_phi * half.x + sin_phi * half.y;
+    let y1 = -sin_phi * half.x + cos_phi * half.y;
+
+    // Scale up the radii if there is no solution
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+    let factor = sign * (numerator / denominator).max(0.0).sqrt();
+    let cx1 = factor * rx * y1 / ry;
+    let cy1 = -factor * ry * x1 / rx;
+    let mid = (from + to) / 2.0;
+    let center = Coord {
+        x: cos_phi * cx1 - sin_phi * cy1 + mid.x,
+        y: sin_phi * cx1 + cos_phi * cy1 + mid.y,
+    };
+
+    let angle = |ux: f64, uy: f64, vx: f64, vy: f64| (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+    let theta = angle(1.0, 0.0, (x1 - cx1) / rx, (y1 - cy1) / ry);
+    let mut delta = angle(
+        (x1 - cx1) / rx,
+        (y1 - cy1) / ry,
+        (-x1 - cx1) / rx,
+        (-y1 - cy1) / ry,
+    );
+    let full_turn = 2.0 * std::f64::consts::PI;
+    if !sweep && delta > 0.0 {
+        delta -= full_turn;
+    } else if sweep && delta < 0.0 {
+        delta += full_turn;
+    }
+
+    // Split into pieces of at most a quarter turn
+    let pieces = (delta.abs() / (full_turn / 4.0)).ceil().max(1.0) as usize;
+    let step = delta / pieces as f64;
+    let k = 4.0 / 3.0 * (step / 4.0).tan();
+    let on_ellipse = |t: f64| {
+        let (sin_t, cos_t) = t.sin_cos();
+        let point = Coord {
+            x: center.x + rx * cos_t * cos_phi - ry * sin_t * sin_phi,
+            y: center.y + rx * cos_t * sin_phi + ry * sin_t * cos_phi,
+        };
+        let derivative = Coord {
+            x: -rx * sin_t * cos_phi - ry * cos_t * sin_phi,
+            y: -rx * sin_t * sin_phi + ry * cos_t * cos_phi,
+        };
+        (point, derivative)
+    };
+    (0..pieces)
+        .map(|i| {
+            let t0 = theta + step * i as f64;
+            let t1 = t0 + step;
+            let (p0, d0) = on_ellipse(t0);
+            let (p3, d3) = on_ellipse(t1);
+            let p0 = if i == 0 { from } else { p0 };
+            let p3 = if i + 1 == pieces { to } else { p3 };
+            Segment::Cubic(p0, p0 + d0 * k, p3 - d3 * k, p3)
+        })
+        .collect()
+}
+
+fn to_coord<T: CoordFloat>(point: &Point<T>) -> Coord {
+    Coord {
+        x: point.x().to_f64().unwrap(),
+        y: point.y().to_f64().unwrap(),
+    }
+}
+
+/// Segments drawn by `command` from `point`, in a sub-path started at `start`.
+/// Moves draw nothing, and neither do arcs and closing lines ending where they start.
+fn command_segments<T: CoordFloat>(
+    command: &PathCommand<T>,
+    point: Coord,
+    start: Coord,
+) -> Vec<Segment> {
+    match command {
+        PathCommand::MoveTo(_) => vec![],
+        PathCommand::LineTo(to) => vec![Segment::Line(point, to_coord(to))],
+        PathCommand::QuadraticTo(control, to) => {
+            vec![Segment::Quadratic(point, to_coord(control), to_coord(to))]
+        }
+        PathCommand::CubicTo(control_1, control_2, to) => vec![Segment::Cubic(
+            point,
+            to_coord(control_1),
+            to_coord(control_2),
+            to_coord(to),
+        )],
+        PathCommand::ArcTo(rx, ry, x_rotation, large_arc, sweep, to) => arc_to_cubics(
+            point,
+            rx.to_f64().unwrap(),
+            ry.to_f64().unwrap(),
+            x_rotation.to_f64().unwrap(),
+            *large_arc,
+            *sweep,
+            to_coord(to),
+        ),
+        PathCommand::Close => match point != start {
+            true => vec![Segment::Line(point, start)],
+            false => vec![],
+        },
+    }
+}
+
+/// Split a [`Path`] into sub-paths of line and Bezier segments.
+/// Arcs are approximated by cubic Bezier curves.
+pub(crate) fn path_sub_paths<T: CoordFloat>(path: &Path<T>) -> Vec<SubPath> {
+    let mut sub_paths: Vec<SubPath> = vec![];
+    let mut current = SubPath::default();
+    let mut start = Coord { x: 0.0, y: 0.0 };
+    let mut point = start;
+    for command in path.commands() {
+        if let PathCommand::MoveTo(to) = command {
+            if !current.segments.is_empty() {
+                sub_paths.push(std::mem::take(&mut current));
+            }
+            start = to_coord(to);
+            point = start;
+            continue;
+        }
+        let segments = command_segments(command, point, start);
+        point = segments.last().map_or(point, Segment::end);
+        current.segments.extend(segments);
+        if *command == PathCommand::Close {
+            current.closed = true;
+            sub_paths.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.segments.is_empty() {
+        sub_paths.push(current);
+    }
+    sub_paths
+}
+
+/// The pieces a [`Path`] draws, one for each line, curve, arc or closing line, as segments.
+/// Moves and pieces that draw nothing are left out.
+pub(crate) fn path_pieces<T: CoordFloat>(path: &Path<T>) -> Vec<Vec<Segment>> {
+    let mut pieces = vec![];
+    let mut start = Coord { x: 0.0, y: 0.0 };
+    let mut point = start;
+    for command in path.commands() {
+        if let PathCommand::MoveTo(to) = command {
+            start = to_coord(to);
+            point = start;
+            continue;
+        }
+        let segments = command_segments(command, point, start);
+        if let Some(last) = segments.last() {
+            point = last.end();
+            pieces.push(segments);
+        }
+    }
+    pieces
+}
@@ -0,0 +1,276 @@
+use super::{Path, PathCommand};
+use crate::art_forms::base_shapes::{star_polygon_outline, try_isotoxal_star_outline};
+use crate::art_forms::leaves::{leaf_outline, LeafStyleDetailed};
+use crate::error::Error;
+
+use geo::{point, Point};
+use std::f64::consts::PI;
+
+type T = f64;
+
+fn assert_close(p: Point<T>, q: Point<T>) {
+    assert!(
+        (p.x() - q.x()).abs() < 1e-9 && (p.y() - q.y()).abs() < 1e-9,
+        "{:?} != {:?}",
+        p,
+        q
+    );
+}
+
+fn square() -> Path<T> {
+    Path::from_points(
+        vec![
+            point!(x: 0.0, y: 0.0),
+            point!(x: 10.0, y: 0.0),
+            point!(x: 10.0, y: 10.0),
+            point!(x: 0.0, y: 10.0),
+        ],
+        true,
+    )
+}
+
+fn circle(radius: T) -> Path<T> {
+    Path::new()
+        .move_to(point!(x: radius, y: 0.0))
+        .arc_to(
+            (radius, radius),
+            0.0,
+            false,
+            true,
+            point!(x: -radius, y: 0.0),
+        )
+        .arc_to(
+            (radius, radius),
+            0.0,
+            false,
+            true,
+            point!(x: radius, y: 0.0),
+        )
+        .close()
+}
+
+#[test]
+fn test_length() {
+    // the closing line counts, the moves between sub-paths don't
+    assert_eq!(square().length(), 40.0);
+    let two_lines = Path::new()
+        .move_to(point!(x: 0.0, y: 0.0))
+        .line_to(point!(x: 3.0, y: 4.0))
+        .move_to(point!(x: 100.0, y: 0.0))
+        .line_to(point!(x: 100.0, y: 1.0));
+    assert_eq!(two_lines.length(), 6.0);
+    // a straight cubic is as long as its chord
+    let cubic: Path<T> = Path::new().move_to(point!(x: 0.0, y: 0.0)).cubic_to(
+        point!(x: 1.0, y: 0.0),
+        point!(x: 8.0, y: 0.0),
+        point!(x: 10.0, y: 0.0),
+    );
+    assert!((cubic.length() - 10.0).abs() < 1e-6);
+    // arcs are measured on their Bezier approximation
+    assert!((circle(10.0).length() - 20.0 * PI).abs() < 1e-2);
+    assert_eq!(Path::<T>::new().length(), 0.0);
+}
+
+#[test]
+fn test_points_along() {
+    let square = square();
+    assert_close(
+        square.point_at_length(15.0).unwrap(),
+        point!(x: 10.0, y: 5.0),
+    );
+    assert_close(
+        square.tangent_at_length(15.0).unwrap(),
+        point!(x: 0.0, y: 1.0),
+    );
+    // the square is counter-clockwise, so the normal points inwards
+    assert_close(
+        square.normal_at_length(15.0).unwrap(),
+        point!(x: -1.0, y: 0.0),
+    );
+    // distances out of the path are clamped to its ends
+    assert_close(
+        square.point_at_length(-1.0).unwrap(),
+        point!(x: 0.0, y: 0.0),
+    );
+    assert_close(
+        square.point_at_length(100.0).unwrap(),
+        point!(x: 0.0, y: 0.0),
+    );
+    assert_eq!(Path::<T>::new().point_at_length(1.0), None);
+
+    // a quarter of the way around a circle, the tangent points back along the x-axis
+    let circle = circle(10.0);
+    let quarter = circle.length() / 4.0;
+    let top = circle.point_at_length(quarter).unwrap();
+    assert!(top.x().abs() < 1e-3 && (top.y() - 10.0).abs() < 1e-3);
+    let tangent = circle.tangent_at_length(quarter).unwrap();
+    assert!((tangent.x() + 1.0).abs() < 1e-3 && tangent.y().abs() < 1e-3);
+}
+
+#[test]
+fn test_points_at_parameters() {
+    // one piece for each side, the closing line included
+    let square = square();
+    let measure = square.measure();
+    assert_eq!(measure.pieces(), 4);
+    assert_eq!(measure.length(), 40.0);
+    assert_close(square.point_at(1.5).unwrap(), point!(x: 10.0, y: 5.0));
+    assert_close(square.tangent_at(1.5).unwrap(), point!(x: 0.0, y: 1.0));
+    assert_close(square.normal_at(3.5).unwrap(), point!(x: 1.0, y: 0.0));
+    // parameters out of the path are clamped to its ends
+    assert_close(measure.point_at(-1.0).unwrap(), point!(x: 0.0, y: 0.0));
+    assert_close(measure.point_at(9.0).unwrap(), point!(x: 0.0, y: 0.0));
+    assert_eq!(Path::<T>::new().measure().point_at(0.5), None);
+
+    // within an arc the parameter is the fraction of its sweep
+    let circle = circle(10.0);
+    assert_eq!(circle.measure().pieces(), 2);
+    assert_close(circle.point_at(0.5).unwrap(), point!(x: 0.0, y: 10.0));
+    assert_close(circle.tangent_at(0.5).unwrap(), point!(x: -1.0, y: 0.0));
+    assert_close(circle.point_at(1.0).unwrap(), point!(x: -10.0, y: 0.0));
+}
+
+#[test]
+fn test_measure() {
+    // the same points as the path, whichever order they are asked for in
+    let circle = circle(10.0);
+    let measure = circle.measure();
+    assert_eq!(measure.length(), circle.length());
+    for distance in [50.0, 0.0, 12.5, 62.8, 31.4] {
+        assert_eq!(
+            measure.point_at_length(distance),
+            circle.point_at_length(distance)
+        );
+    }
+    // on a curve of changing speed the distance is still measured along it
+    let cubic: Path<T> = Path::new().move_to(point!(x: 0.0, y: 0.0)).cubic_to(
+        point!(x: 1.0, y: 0.0),
+        point!(x: 8.0, y: 0.0),
+        point!(x: 10.0, y: 0.0),
+    );
+    let point = cubic.point_at_length(3.0).unwrap();
+    assert!((point.x() - 3.0).abs() < 1e-6 && point.y() == 0.0);
+}
+
+#[test]
+fn test_flatten() {
+    let tolerance = 0.01;
+    let lines = circle(10.0).flatten(tolerance);
+    assert_eq!(lines.len(), 1);
+    let line = &lines[0];
+    assert!(line.is_closed());
+    // the middle of every line is within the tolerance of the circle
+    for segment in line.lines() {
+        let middle = (segment.start + segment.end) / 2.0;
+        assert!(10.0 - middle.x.hypot(middle.y) < tolerance + 1e-3);
+    }
+    let coarse = circle(10.0).flatten(1.0);
+    assert!(coarse[0].0.len() < line.0.len());
+    // lines are kept as they are
+    assert_eq!(square().flatten(tolerance)[0].0.len(), 5);
+    // every sub-path gives its own line string
+    let path = Path::new()
+        .move_to(point!(x: 0.0, y: 0.0))
+        .line_to(point!(x: 10.0, y: 0.0))
+        .move_to(point!(x: 0.0, y: 5.0))
+        .line_to(point!(x: 10.0, y: 5.0))
+        .line_to(point!(x: 10.0, y: 10.0));
+    let lines = path.flatten(tolerance);
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].0.len(), 2);
+    assert_eq!(lines[1].0.len(), 3);
+    assert!(!lines[1].is_closed());
+}
+
+#[test]
+fn test_reverse() {
+    let path: Path<T> = Path::new()
+        .move_to(point!(x: 0.0, y: 0.0))
+        .line_to(point!(x: 10.0, y: 0.0))
+        .quadratic_to(point!(x: 15.0, y: 5.0), point!(x: 10.0, y: 10.0))
+        .cubic_to(
+            point!(x: 8.0, y: 12.0),
+            point!(x: 2.0, y: 12.0),
+            point!(x: 0.0, y: 10.0),
+        )
+        .arc_to((5.0, 5.0), 0.0, false, true, point!(x: 0.0, y: 0.0));
+    let reversed = path.reverse();
+    assert_eq!(
+        reversed.commands(),
+        &[
+            PathCommand::MoveTo(point!(x: 0.0, y: 0.0)),
+            PathCommand::ArcTo(5.0, 5.0, 0.0, false, false, point!(x: 0.0, y: 10.0)),
+            PathCommand::CubicTo(
+                point!(x: 2.0, y: 12.0),
+                point!(x: 8.0, y: 12.0),
+                point!(x: 10.0, y: 10.0)
+            ),
+            PathCommand::QuadraticTo(point!(x: 15.0, y: 5.0), point!(x: 10.0, y: 0.0)),
+            PathCommand::LineTo(point!(x: 0.0, y: 0.0)),
+        ]
+    );
+    assert_eq!(reversed.reverse(), path);
+    assert!((reversed.length() - path.length()).abs() < 1e-9);
+
+    // a closed path is walked the other way from the same start
+    let square = square().reverse();
+    assert_eq!(
+        square.commands()[0],
+        PathCommand::MoveTo(point!(x: 0.0, y: 0.0))
+    );
+    assert_eq!(
+        square.commands()[1],
+        PathCommand::LineTo(point!(x: 0.0, y: 10.0))
+    );
+    assert_eq!(square.commands().last(), Some(&PathCommand::Close));
+    // now clockwise, the normal points outwards
+    assert_close(
+        square.normal_at_length(5.0).unwrap(),
+        point!(x: -1.0, y: 0.0),
+    );
+}
+
+#[test]
+fn test_bounds() {
+    let bounds = circle(10.0).bounds().unwrap();
+    assert!((bounds.min().x + 10.0).abs() < 1e-2 && (bounds.max().y - 10.0).abs() < 1e-2);
+    // the bounds of a curve are tighter than its control points
+    let arch: Path<T> = Path::new()
+        .move_to(point!(x: 0.0, y: 0.0))
+        .quadratic_to(point!(x: 5.0, y: 10.0), point!(x: 10.0, y: 0.0));
+    assert!((arch.bounds().unwrap().max().y - 5.0).abs() < 1e-9);
+    assert_eq!(
+        Path::<T>::new().move_to(point!(x: 1.0, y: 1.0)).bounds(),
+        None
+    );
+}
+
+#[test]
+fn test_outlines() {
+    // the pentagram has five edges spanning two fifths of the circle each
+    let pentagram = star_polygon_outline(10.0, point!(x: 0.0, y: 0.0), 90.0, 5, 2);
+    assert!((pentagram.length() - 5.0 * 20.0 * (0.4 * PI).sin()).abs() < 1e-6);
+    // the hexagram is two triangles
+    let hexagram = star_polygon_outline(10.0, point!(x: 0.0, y: 0.0), 90.0, 6, 2);
+    let moves = hexagram
+        .commands()
+        .iter()
+        .filter(|command| matches!(command, PathCommand::MoveTo(_)))
+        .count();
+    assert_eq!(moves, 2);
+    assert_eq!(
+        try_isotoxal_star_outline(10.0, point!(x: 0.0, y: 0.0), 0.0, 8, 2, None),
+        Err(Error::NotCoprime { p: 8, q: 2 })
+    );
+
+    // halfway around a symmetric leaf is its tip
+    let tip: Point<T> = point!(x: 0.0, y: 20.0);
+    let leaf = leaf_outline(
+        point!(x: -5.0, y: 0.0),
+        point!(x: 5.0, y: 0.0),
+        tip,
+        LeafStyleDetailed::Linear(5.0, 0.0, 5.0, 0.0),
+    );
+    let halfway = leaf.point_at_length((leaf.length() - 10.0) / 2.0).unwrap();
+    assert!((halfway.x() - tip.x()).abs() < 1e-6 && (halfway.y() - tip.y()).abs() < 1e-6);
+}
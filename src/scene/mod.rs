@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+pub mod scene;
+pub use scene::{Geometry, Scene, Shape};
+
+#[cfg(test)]
+pub mod test_scene;
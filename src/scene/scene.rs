@@ -0,0 +1,290 @@
+//! Retained description of everything drawn, which backends are rendered from.
+//! Shapes keep their geometry, style and id, so they can be queried and restyled
+//! after construction and rendered any number of times.
+use geo::{Coord, Point, Rect};
+use geo_types::CoordFloat;
+
+use crate::art_forms::base_shapes::{Config, FillRule};
+use crate::backend::Backend;
+use crate::color::Theme;
+use crate::path::Path;
+use crate::path::segment::path_sub_paths;
+
+/// Geometry of a shape, given in the coordinates of the art forms.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Geometry<T: CoordFloat> {
+    /// A circle given by its radius and center.
+    Circle(T, Point<T>),
+    /// An arbitrary path.
+    Path(Path<T>),
+}
+
+/// A styled geometry with an optional id.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shape<T: CoordFloat> {
+    pub geometry: Geometry<T>,
+    pub config: Config<T>,
+    pub id: Option<String>,
+}
+
+impl<T: CoordFloat> Shape<T> {
+    /// Bounding box of the shape, strokes included.
+    pub fn bounds(&self) -> Option<Rect<T>> {
+        let rect = match &self.geometry {
+            Geometry::Circle(radius, center) => {
+                let radius = Coord {
+                    x: *radius,
+                    y: *radius,
+                };
+                Rect::new(center.0 - radius, center.0 + radius)
+            }
+            Geometry::Path(path) => path.bounds()?,
+        };
+        let stroke = match self.config.stroke().is_none() {
+            true => T::zero(),
+            false => self.config.stroke_width / (T::one() + T::one()),
+        };
+        let stroke = Coord {
+            x: stroke,
+            y: stroke,
+        };
+        Some(Rect::new(rect.min() - stroke, rect.max() + stroke))
+    }
+
+    /// Whether the fill or the stroke of the shape covers `point`.
+    /// The fill follows the fill rule of the shape, Bezier curves are tested exactly.
+    pub fn contains(&self, point: Point<T>) -> bool {
+        self.intersects(Rect::new(point.0, point.0))
+    }
+
+    /// Whether the fill or the stroke of the shape covers some part of `rect`.
+    pub fn intersects(&self, rect: Rect<T>) -> bool {
+        let coord = |coord: Coord<T>| Coord {
+            x: coord.x.to_f64().unwrap(),
+            y: coord.y.to_f64().unwrap(),
+        };
+        let rect = Rect::new(coord(rect.min()), coord(rect.max()));
+        let filled = !self.config.fill().is_none();
+        let half_width = match self.config.stroke().is_none() {
+            true => None,
+            false => Some(self.config.stroke_width.to_f64().unwrap() / 2.0)
+                .filter(|half_width| *half_width > 0.0),
+        };
+        match &self.geometry {
+            Geometry::Circle(radius, center) => {
+                let (radius, center) = (radius.to_f64().unwrap(), coord(center.0));
+                let dx = (rect.min().x - center.x)
+                    .max(center.x - rect.max().x)
+                    .max(0.0);
+                let dy = (rect.min().y - center.y)
+                    .max(center.y - rect.max().y)
+                    .max(0.0);
+                let nearest = dx.hypot(dy);
+                let dx = (center.x - rect.min().x).max(rect.max().x - center.x);
+                let dy = (center.y - rect.min().y).max(rect.max().y - center.y);
+                let farthest = dx.hypot(dy);
+                (filled && nearest <= radius)
+                    || half_width.is_some_and(|half_width| {
+                        nearest <= radius + half_width && farthest >= radius - half_width
+                    })
+            }
+            Geometry::Path(path) => {
+                let sub_paths = path_sub_paths(path);
+                let meets = |distance: f64| {
+                    sub_paths
+                        .iter()
+                        .flat_map(|sub_path| sub_path.segments.iter())
+                        .any(|segment| segment.meets_rect(rect, distance))
+                };
+                let (min, max) = (rect.min(), rect.max());
+                let corners = [
+                    min,
+                    Coord { x: min.x, y: max.y },
+                    max,
+                    Coord { x: max.x, y: min.y },
+                ];
+                let covers = |corner: &Coord| {
+                    let winding: i32 = sub_paths
+                        .iter()
+                        .map(|sub_path| sub_path.winding_number(*corner))
+                        .sum();
+                    match self.config.fill_rule {
+                        FillRule::NonZero => winding != 0,
+                        FillRule::EvenOdd => winding % 2 != 0,
+                    }
+                };
+                (filled && (corners.iter().any(covers) || meets(0.0)))
+                    || half_width.is_some_and(meets)
+            }
+        }
+    }
+
+    /// Draw the shape on a backend.
+    pub fn draw<B: Backend<T>>(&self, backend: B) -> B {
+        backend.draw_shape(self)
+    }
+}
+
+/// The shapes drawn so far, from bottom to top.
+/// A scene is itself a backend, so every art form can be drawn on it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scene<T: CoordFloat> {
+    shapes: Vec<Shape<T>>,
+}
+
+impl<T: CoordFloat> Default for Scene<T> {
+    fn default() -> Self {
+        Scene { shapes: vec![] }
+    }
+}
+
+impl<T: CoordFloat> Scene<T> {
+    /// Create an empty scene.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All shapes from bottom to top.
+    pub fn shapes(&self) -> &[Shape<T>] {
+        &self.shapes
+    }
+
+    /// The first shape with `id`.
+    pub fn shape(&self, id: &str) -> Option<&Shape<T>> {
+        self.shapes
+            .iter()
+            .find(|shape| shape.id.as_deref() == Some(id))
+    }
+
+    /// The first shape with `id`, for editing.
+    pub fn shape_mut(&mut self, id: &str) -> Option<&mut Shape<T>> {
+        self.shapes
+            .iter_mut()
+            .find(|shape| shape.id.as_deref() == Some(id))
+    }
+
+    /// Give `id` to the shape drawn last.
+    pub fn id(mut self, id: &str) -> Self {
+        if let Some(shape) = self.shapes.last_mut() {
+            shape.id = Some(id.to_string());
+        }
+        self
+    }
+
+    /// Draw with `draw` and give the new shapes the ids `prefix-0`, `prefix-1`, ...
+    /// in the order they are drawn.
+    pub fn with_ids<F>(self, prefix: &str, draw: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        let start = self.shapes.len();
+        let mut scene = draw(self);
+        for (index, shape) in scene.shapes.iter_mut().skip(start).enumerate() {
+            shape.id = Some(format!("{}-{}", prefix, index));
+        }
+        scene
+    }
+
+    /// Replace the style of every shape with `id`.
+    pub fn restyle(mut self, id: &str, config: Config<T>) -> Self {
+        self.shapes
+            .iter_mut()
+            .filter(|shape| shape.id.as_deref() == Some(id))
+            .for_each(|shape| shape.config = config.clone());
+        self
+    }
+
+    /// The scene with every shape recoloured by `theme`.
+    /// The current colours are recoloured, so themes applied one after the other compose,
+    /// e.g. a dark theme of a monochrome scene. To switch themes, apply each to the scene as it was drawn.
+    pub fn with_theme(&self, theme: Theme) -> Self {
+        Scene {
+            shapes: self
+                .shapes
+                .iter()
+                .map(|shape| Shape {
+                    config: theme.config(&shape.config),
+                    ..shape.clone()
+                })
+                .collect(),
+        }
+    }
+
+    /// Remove every shape with `id`.
+    pub fn remove(mut self, id: &str) -> Self {
+        self.shapes.retain(|shape| shape.id.as_deref() != Some(id));
+        self
+    }
+
+    /// Bounding box of all shapes, strokes included.
+    pub fn bounds(&self) -> Option<Rect<T>> {
+        self.shapes
+            .iter()
+            .filter_map(|shape| shape.bounds())
+            .reduce(|rect, other| {
+                Rect::new(
+                    Coord {
+                        x: rect.min().x.min(other.min().x),
+                        y: rect.min().y.min(other.min().y),
+                    },
+                    Coord {
+                        x: rect.max().x.max(other.max().x),
+                        y: rect.max().y.max(other.max().y),
+                    },
+                )
+            })
+    }
+
+    /// Shapes covering `point`, from top to bottom.
+    pub fn shapes_at(&self, point: Point<T>) -> Vec<&Shape<T>> {
+        self.shapes_in(Rect::new(point.0, point.0))
+    }
+
+    /// Shapes covering some part of `rect`, from top to bottom.
+    pub fn shapes_in(&self, rect: Rect<T>) -> Vec<&Shape<T>> {
+        self.shapes
+            .iter()
+            .rev()
+            .filter(|shape| {
+                shape.bounds().is_some_and(|bounds| {
+                    bounds.min().x <= rect.max().x
+                        && rect.min().x <= bounds.max().x
+                        && bounds.min().y <= rect.max().y
+                        && rect.min().y <= bounds.max().y
+                })
+            })
+            .filter(|shape| shape.intersects(rect))
+            .collect()
+    }
+
+    /// Draw all shapes on `backend`, from bottom to top.
+    pub fn render<B: Backend<T>>(&self, backend: B) -> B {
+        self.shapes
+            .iter()
+            .fold(backend, |backend, shape| shape.draw(backend))
+    }
+}
+
+/*====== Recording shapes ============ */
+impl<T: CoordFloat> Backend<T> for Scene<T> {
+    fn draw_circle(self, radius: T, center: Point<T>, config: Config<T>) -> Self {
+        self.draw_shape(&Shape {
+            geometry: Geometry::Circle(radius, center),
+            config,
+            id: None,
+        })
+    }
+
+    fn draw_path(self, path: Path<T>, config: Config<T>) -> Self {
+        self.draw_shape(&Shape {
+            geometry: Geometry::Path(path),
+            config,
+            id: None,
+        })
+    }
+
+    fn draw_shape(mut self, shape: &Shape<T>) -> Self {
+        self.shapes.push(shape.clone());
+        self
+    }
+}
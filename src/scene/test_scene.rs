@@ -0,0 +1,156 @@
+use super::{Geometry, Scene};
+use crate::art_forms::base_shapes::{BaseShapes, Config};
+use crate::art_forms::leaves::{LeafStyle, Leaves};
+use crate::art_forms::yantra::Yantra;
+use crate::backend::RasterCanvas;
+use crate::color::Color;
+use crate::canvas::Canvas as _;
+
+use crate::canvas::SvgCanvas;
+use geo::{point, Rect};
+
+type Canvas = SvgCanvas;
+type T = f64;
+
+fn sri_scene() -> Scene<T> {
+    sri_scene_with_leaves_from(0.0)
+}
+
+/// The Sri Yantra scene with its ring of leaves starting at `leaves_start` degrees.
+fn sri_scene_with_leaves_from(leaves_start: T) -> Scene<T> {
+    let center = point!(x: 0.0, y: 0.0);
+    let radius = 100.0;
+    let outer = Config::<T>::try_new(1.0, "none", "#7c96f7").unwrap();
+    let inner = Config::<T>::try_new(1.0, "none", "#E8FB62").unwrap();
+    let configs = [0, 1, 2, 3, 4, 5, 6, 7, 8].map(|i| match i % 2 {
+        0 => outer.clone(),
+        _ => inner.clone(),
+    });
+    let leaf_config = Config::<T>::try_new(2.0, "#900007", "pink").unwrap();
+    let reniform_style = LeafStyle::<T>::new_reniform(25.0, 1.1, 12.0, 13.0);
+    Scene::new()
+        .add_circle(radius, center, inner.clone())
+        .id("background")
+        .with_ids("leaf", |scene| {
+            scene.add_circular_leaves(
+                radius,
+                center,
+                leaves_start,
+                16,
+                1.0,
+                reniform_style,
+                leaf_config,
+            )
+        })
+        .with_ids("sri", |scene| scene.add_sri(radius, center, configs))
+}
+
+#[test]
+fn test_scene_ids_and_restyle() {
+    let scene = sri_scene();
+    assert_eq!(scene.shapes().len(), 1 + 16 + 9);
+    assert!(matches!(
+        scene.shape("background").unwrap().geometry,
+        Geometry::Circle(..)
+    ));
+    assert!(scene.shape("sri-8").is_some());
+    assert!(scene.shape("sri-9").is_none());
+
+    let red = Config::<T>::try_new(1.0, "none", "red").unwrap();
+    let mut scene = scene.restyle("sri-4", red).remove("leaf-0");
+    assert_eq!(scene.shapes().len(), 1 + 15 + 9);
+    assert_eq!(scene.shape("sri-4").unwrap().config.fill(), "red");
+    scene.shape_mut("background").unwrap().config.fill_color = Color::WHITE.to_string();
+
+    let view_size = 150.0;
+    let canvas = scene.render(Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    ));
+    let content = canvas.to_string();
+    assert!(content.contains(r##"fill="#ff0000" id="sri-4""##));
+    assert!(content.contains(r##"fill="#ffffff" id="background""##));
+    svg::save("./unit_tests/scene/restyle.svg", &canvas).unwrap();
+}
+
+#[test]
+fn test_scene_bounds_and_rendering() {
+    let scene = sri_scene();
+    let canvas = scene.render(Canvas::new());
+    let bounds = scene.bounds().unwrap();
+    let content_bounds: Rect<T> = canvas.content_bounds().unwrap();
+    for (coord, expected) in [
+        (bounds.min(), content_bounds.min()),
+        (bounds.max(), content_bounds.max()),
+    ] {
+        assert!((coord.x - expected.x).abs() < 1e-6);
+        assert!((coord.y - expected.y).abs() < 1e-6);
+    }
+
+    // the same scene is rendered again without constructing it again
+    let view_box = Rect::new(point!(x: -150.0, y: -150.0), point!(x: 150.0, y: 150.0));
+    let raster = scene.render(RasterCanvas::new(300, 300, view_box));
+    assert_eq!(raster.pixel(0, 0), [0, 0, 0, 0]);
+    assert_eq!(raster.pixel(150, 150)[3], 255);
+}
+
+#[test]
+fn test_hit_testing() {
+    // the leaves are turned by half a leaf, so that the first one is centered on the positive x-axis
+    let scene = sri_scene_with_leaves_from(-360.0 / 32.0);
+    let ids = |shapes: Vec<&super::Shape<T>>| -> Vec<String> {
+        shapes
+            .into_iter()
+            .map(|shape| shape.id.clone().unwrap_or_default())
+            .collect()
+    };
+
+    // the bindu is the innermost triangle, on top of the others
+    let hits = ids(scene.shapes_at(point!(x: 0.0, y: 0.0)));
+    assert_eq!(hits.first().map(String::as_str), Some("sri-8"));
+    assert_eq!(hits.last().map(String::as_str), Some("background"));
+
+    // the leaf tip on the positive x-axis is only covered by the curved outline of the first leaf
+    assert_eq!(ids(scene.shapes_at(point!(x: 120.0, y: 0.0))), ["leaf-0"]);
+    // between the tips of two leaves nothing is hit, although the point is in their bounding boxes
+    let (sin, cos) = (360.0f64 / 32.0).to_radians().sin_cos();
+    let between = point!(x: 120.0 * cos, y: 120.0 * sin);
+    assert!(scene.shape("leaf-1").unwrap().bounds().unwrap().max().x > between.x());
+    assert!(scene.shapes_at(between).is_empty());
+    // outside everything
+    assert!(scene.shapes_at(point!(x: 140.0, y: 140.0)).is_empty());
+
+    // the stroke of a leaf counts, even outside its fill
+    let leaf = scene.shape("leaf-0").unwrap();
+    let tip = leaf.bounds().unwrap().max().x;
+    assert!(leaf.contains(point!(x: tip - 0.5, y: 0.0)));
+    assert!(!leaf.contains(point!(x: tip + 0.5, y: 0.0)));
+
+    // rectangles hit everything they overlap
+    let rect = Rect::new(point!(x: 115.0, y: -1.0), point!(x: 200.0, y: 1.0));
+    assert_eq!(ids(scene.shapes_in(rect)), ["leaf-0"]);
+    let rect = Rect::new(point!(x: -200.0, y: -200.0), point!(x: 200.0, y: 200.0));
+    assert_eq!(scene.shapes_in(rect).len(), scene.shapes().len());
+}
+
+#[test]
+fn test_hit_testing_follows_fill_rule() {
+    use crate::art_forms::base_shapes::FillRule;
+    let config = Config::<T>::try_new(0.0, "none", "black").unwrap();
+    let scene = Scene::new()
+        .add_star_polygon(40.0, point!(x: 0.0, y: 0.0), 90.0, 5, 2, config.clone())
+        .id("nonzero")
+        .add_star_polygon(
+            40.0,
+            point!(x: 100.0, y: 0.0),
+            90.0,
+            5,
+            2,
+            config.with_fill_rule(FillRule::EvenOdd),
+        )
+        .id("evenodd");
+    assert!(scene.shape("nonzero").unwrap().contains(point!(x: 0.0, y: 0.0)));
+    assert!(!scene.shape("evenodd").unwrap().contains(point!(x: 100.0, y: 0.0)));
+    // a spike of the star is covered once
+    assert!(scene.shape("evenodd").unwrap().contains(point!(x: 100.0, y: 30.0)));
+}
@@ -3,7 +3,8 @@ use geo::line_intersection::{line_intersection, LineIntersection};
 use geo::prelude::*;
 use geo_types::CoordFloat;
 use geo::GeoFloat;
-// use numeric_literals::replace_numeric_literals;
+use crate::error::{Error, Result};
+use numeric_literals::replace_numeric_literals;
 
 pub fn polar_point<T: CoordFloat>(radius: T, center: Point<T>, alpha: T) -> Point<T> {
     center
@@ -18,15 +19,15 @@ pub fn dist<T: CoordFloat>(p: Point<T>, q: Point<T>) -> T {
 }
 
 /// Get the intersection of lines p--q and r--s.
-pub fn intersection<T: CoordFloat + GeoFloat>(p: Point<T>, q: Point<T>, r: Point<T>, s: Point<T>) -> Point<T> {
-    let intersection = line_intersection(Line::new(p,q), Line::new(r,s));
-    match intersection.expect("Intersection not found") {
-        LineIntersection::SinglePoint{ intersection: point, ..}  => Point::from(point),
-        LineIntersection::Collinear{ .. } => panic!("Intersection not found")
+pub fn intersection<T: CoordFloat + GeoFloat>(p: Point<T>, q: Point<T>, r: Point<T>, s: Point<T>) -> Result<Point<T>> {
+    match line_intersection(Line::new(p,q), Line::new(r,s)) {
+        Some(LineIntersection::SinglePoint{ intersection: point, ..})  => Ok(Point::from(point)),
+        Some(LineIntersection::Collinear{ .. }) | None => Err(Error::NoIntersection)
     }
 }
 
-// #[replace_numeric_literals(T::from(literal).unwrap())]
-// pub fn golden_ratio<T: CoordFloat>() -> T {
-//    (1 + 5.sqrt()) / 2
-// }
\ No newline at end of file
+/// The golden ratio φ, the ratio of the sides of a golden rectangle.
+#[replace_numeric_literals(T::from(literal).unwrap())]
+pub fn golden_ratio<T: CoordFloat>() -> T {
+    (1 + 5.sqrt()) / 2
+}
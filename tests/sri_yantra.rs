@@ -44,3 +44,63 @@ fn new_config<T: CoordFloat>(mut config: Config<T>, new_fill_color: &str) -> Con
     config.fill_color = new_fill_color.to_string();
     config
 }
+
+fn palette_sri_yantra<B: Backend<f64>>(backend: B, palette: &Palette<f64>) -> B {
+    let center = point!(x: 0.0, y: 0.0);
+    let radius = 100.0;
+    let cordate_style = LeafStyle::new_cordate(15.0, 1.02, 12.0, 13.0);
+    let reniform_style = LeafStyle::new_reniform(25.0, 1.1, 12.0, 13.0);
+    backend
+    .add_circle(radius, center, palette.config("background"))
+    .add_circular_leaves(radius, center, -360.0 / 32.0, 16, 1.0, reniform_style, palette.config("outer petals"))
+    .add_circle(radius, center, palette.config("background"))
+    .add_circular_leaves(radius, center, -360.0 / 16.0, 8, 1.0, cordate_style, palette.config("inner petals"))
+    .add_sri(radius, center, palette.configs())
+    .add_circle(1.0, center, palette.config("bindu"))
+}
+
+/// Fills of all shapes in the order they are drawn.
+fn fills(canvas: &Canvas) -> Vec<String> {
+    let content = canvas.to_string();
+    svg::read(&content)
+        .unwrap()
+        .filter_map(|event| match event {
+            svg::parser::Event::Tag("circle" | "path", _, attributes) => {
+                attributes.get("fill").map(|fill| fill.to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn sri_yantra_themes() {
+    let view_size = 150.0;
+    let canvas: Canvas = Canvas::new().set(
+        "viewBox",
+        (-view_size, -view_size, 2.0 * view_size, 2.0 * view_size),
+    );
+    let palette = Palette::sri_yantra();
+    let original = fills(&palette_sri_yantra(canvas.clone(), &palette));
+    // background, petals, background, petals, the nine parts of the sri and the bindu
+    assert_eq!(original.len(), 1 + 16 + 1 + 8 + 9 + 1);
+    assert_eq!(original.last().unwrap(), &Color::rgb(0xf3, 0x36, 0x25).to_string());
+
+    for (theme, name) in [(Theme::Monochrome, "monochrome"), (Theme::Dark, "dark")] {
+        let yantra = palette_sri_yantra(Themed::new(canvas.clone(), theme), &palette).into_inner();
+        svg::save(format!("./examples/sri_yantra_{}.svg", name), &yantra).unwrap();
+        let themed = fills(&yantra);
+        assert_eq!(themed.len(), original.len());
+        assert_ne!(themed, original);
+        for (fill, original) in themed.iter().zip(&original) {
+            let color = original.parse::<Color>().unwrap();
+            assert_eq!(fill, &theme.color(&color).to_string());
+        }
+    }
+    // monochrome keeps only greys
+    let monochrome = palette_sri_yantra(Themed::new(canvas, Theme::Monochrome), &palette).into_inner();
+    for fill in fills(&monochrome) {
+        let [red, green, blue, _] = fill.parse::<Color>().unwrap().to_rgba8();
+        assert!(red == green && green == blue, "{} is not grey", fill);
+    }
+}